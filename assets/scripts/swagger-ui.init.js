@@ -0,0 +1,8 @@
+window.addEventListener("load", () => {
+    const element = document.getElementById("swagger-ui");
+    window.ui = SwaggerUIBundle({
+        url: element.dataset.url,
+        domNode: element,
+        deepLinking: true,
+    });
+});
@@ -0,0 +1,164 @@
+locale-name = English
+locale-auto = Automatic
+app-title = Messages
+
+location-authentication = Authentication
+location-dashboard = Dashboard
+location-audit = Audit
+location-conversations = Conversations
+location-notifications = Notifications
+location-error = Error
+
+nav-dashboard = Dashboard
+nav-conversations = Conversations
+nav-audit = Audit
+nav-logout = Logout
+nav-language = Language
+nav-notifications = Notifications
+
+footer-backend = Backend
+footer-frontend = Frontend
+
+auth-login-title = LOGIN
+auth-login-prompt = Please enter your name and password!
+auth-login-switch = Don't have an account?
+auth-login = Login
+auth-register-title = REGISTER
+auth-register-prompt = Please enter a name and password!
+auth-register-switch = Already have an account?
+auth-register = Register
+auth-invalid = Invalid credentials.
+
+dashboard-title = NEW MESSAGE
+dashboard-prompt = Please enter a title and content for #{ $channel }!
+dashboard-send = Send
+dashboard-hint = Feel free to expand the content area as needed.
+
+channel-title = CHANNELS
+channel-private = Private
+channel-create = Create channel
+channel-members = Members
+channel-add-member = Add member
+
+conversation-new-title = NEW CONVERSATION
+conversation-prompt = Please enter the names of the participants and a message!
+conversation-index-title = CONVERSATIONS
+conversation-unread =
+    { $count ->
+        [one] 1 unread
+       *[other] { $count } unread
+    }
+conversation-empty = No conversations yet.
+conversation-back = All conversations
+
+message-index-title = MESSAGES
+message-show-title = MESSAGE #{ $id }
+message-view = View
+message-edit = Edit
+message-delete = Delete
+message-close = Close
+message-preview = Preview (Markdown)
+message-preview-empty = Nothing to preview.
+message-replies =
+    { $count ->
+        [one] 1 reply
+       *[other] { $count } replies
+    }
+message-reply = Reply
+message-parent = Parent
+message-reaction = React with { $reaction }
+reaction-like = thumbs up
+reaction-love = heart
+reaction-laugh = laughing face
+reaction-wow = surprised face
+reaction-sad = sad face
+reaction-party = party popper
+message-pinned = Pinned
+message-pin = Pin
+message-unpin = Unpin
+message-star = Star
+message-unstar = Unstar
+message-filter-all = All
+message-filter-starred = Starred
+message-starred-empty = You have not starred any messages in this channel.
+message-tagged-empty = No messages have all of the selected tags.
+notification-mention = { $actor } mentioned you in message #{ $message }.
+notification-empty = No notifications yet.
+notification-reply = { $actor } replied to your message #{ $message }.
+notification-edit = { $actor } edited your message #{ $message }.
+notification-admin-message_pin = { $actor } pinned your message #{ $message }.
+notification-admin-message_unpin = { $actor } unpinned your message #{ $message }.
+notification-admin-message_delete = { $actor } deleted one of your messages.
+notification-admin-user_set_password = An administrator changed your password.
+notification-admin-user_set_role = An administrator changed your role.
+notification-admin-user_enable = An administrator enabled your account.
+notification-admin-user_issue_token = An administrator issued you a new API token.
+notification-admin-user_revoke_token = An administrator revoked your API token.
+notification-all = All notifications
+notification-read = Mark as read
+notification-read-all = Mark all as read
+notification-index-title = Notifications
+notification-preferences-title = Notify me about
+notification-preference-mention = Mentions of me
+notification-preference-reply = Replies to my messages
+notification-preference-edit = Edits to my messages
+notification-preference-admin = Moderator and administrator actions
+notification-page = Page { $page } of { $pages }
+notification-previous = Previous
+notification-next = Next
+
+audit-title = AUDIT LOG
+audit-actor = Actor
+audit-action = Action
+audit-any = Any
+audit-from = From
+audit-to = To
+audit-export-csv = Export CSV
+audit-export-json = Export JSON
+audit-time = Time
+audit-target = Target
+audit-ip = IP
+audit-user-agent = User Agent
+audit-empty = No events.
+
+error-back = Back
+error-unauthorized = Authentication is required.
+error-not-found = The requested resource does not exist.
+error-conflict = The request conflicts with existing data.
+error-internal = Something went wrong, please try again.
+
+toast-message-sent = Message #{ $id } sent.
+toast-message-deleted = Message #{ $id } deleted.
+toast-reply-sent = Reply #{ $id } sent.
+toast-notification-preferences = Notification preferences saved.
+toast-registered = Successful registration.
+toast-channel-member-added = { $name } added to the channel.
+
+field-title = Title
+field-content = Content
+field-name = Name
+field-password = Password
+field-participants = Participants
+field-channel = Channel name
+field-tags = Tags, separated by commas
+field-reaction = Reaction
+field-from = From
+field-to = To
+
+validation-blank = { $field } must not consist of whitespace only.
+validation-min-length = { $field } must be at least { $min ->
+        [one] 1 character
+       *[other] { $min } characters
+    } long.
+validation-max-length = { $field } must not be more than { $max ->
+        [one] 1 character
+       *[other] { $max } characters
+    } long.
+validation-invalid = { $field } is invalid.
+validation-taken = Name already taken.
+validation-unknown-user = No such user.
+validation-member = Already a member.
+validation-participants = Enter between 1 and { $max } other participants.
+validation-tags = Enter at most { $max } tags of at most { $length } characters each.
+validation-unknown-participant = No such user: { $name }.
+validation-internal = Internal server error.
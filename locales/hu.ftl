@@ -0,0 +1,150 @@
+locale-name = Magyar
+locale-auto = Automatikus
+app-title = Üzenetek
+
+location-authentication = Hitelesítés
+location-dashboard = Irányítópult
+location-audit = Napló
+location-conversations = Beszélgetések
+location-notifications = Értesítések
+location-error = Hiba
+
+nav-dashboard = Irányítópult
+nav-conversations = Beszélgetések
+nav-audit = Napló
+nav-logout = Kijelentkezés
+nav-language = Nyelv
+nav-notifications = Értesítések
+
+footer-backend = Backend
+footer-frontend = Frontend
+
+auth-login-title = BEJELENTKEZÉS
+auth-login-prompt = Add meg a neved és a jelszavad!
+auth-login-switch = Még nincs fiókod?
+auth-login = Bejelentkezés
+auth-register-title = REGISZTRÁCIÓ
+auth-register-prompt = Adj meg egy nevet és egy jelszót!
+auth-register-switch = Már van fiókod?
+auth-register = Regisztráció
+auth-invalid = Érvénytelen hitelesítő adatok.
+
+dashboard-title = ÚJ ÜZENET
+dashboard-prompt = Adj meg egy címet és egy tartalmat a(z) #{ $channel } csatornába!
+dashboard-send = Küldés
+dashboard-hint = A tartalom mezője szükség szerint nagyítható.
+
+channel-title = CSATORNÁK
+channel-private = Privát
+channel-create = Csatorna létrehozása
+channel-members = Tagok
+channel-add-member = Tag hozzáadása
+
+conversation-new-title = ÚJ BESZÉLGETÉS
+conversation-prompt = Add meg a résztvevők nevét és egy üzenetet!
+conversation-index-title = BESZÉLGETÉSEK
+conversation-unread = { $count } olvasatlan
+conversation-empty = Még nincsenek beszélgetések.
+conversation-back = Összes beszélgetés
+
+message-index-title = ÜZENETEK
+message-show-title = ÜZENET #{ $id }
+message-view = Megnyitás
+message-edit = Szerkesztés
+message-delete = Törlés
+message-close = Bezárás
+message-preview = Előnézet (Markdown)
+message-preview-empty = Nincs mit megjeleníteni.
+message-replies = { $count } válasz
+message-reply = Válasz
+message-parent = Előzmény
+message-reaction = Reakció: { $reaction }
+reaction-like = felfelé mutató hüvelykujj
+reaction-love = szív
+reaction-laugh = nevető arc
+reaction-wow = meglepett arc
+reaction-sad = szomorú arc
+reaction-party = partikürt
+message-pinned = Kitűzött
+message-pin = Kitűzés
+message-unpin = Kitűzés megszüntetése
+message-star = Csillagozás
+message-unstar = Csillag eltávolítása
+message-filter-all = Összes
+message-filter-starred = Csillagozott
+message-starred-empty = Ebben a csatornában még nem csillagoztál meg üzenetet.
+message-tagged-empty = Egyetlen üzenet sem rendelkezik az összes kiválasztott címkével.
+notification-mention = { $actor } megemlített a(z) #{ $message } üzenetben.
+notification-empty = Még nincsenek értesítések.
+notification-reply = { $actor } válaszolt a(z) #{ $message } üzenetedre.
+notification-edit = { $actor } szerkesztette a(z) #{ $message } üzenetedet.
+notification-admin-message_pin = { $actor } kitűzte a(z) #{ $message } üzenetedet.
+notification-admin-message_unpin = { $actor } levette a kitűzöttek közül a(z) #{ $message } üzenetedet.
+notification-admin-message_delete = { $actor } törölte az egyik üzenetedet.
+notification-admin-user_set_password = Egy adminisztrátor megváltoztatta a jelszavadat.
+notification-admin-user_set_role = Egy adminisztrátor megváltoztatta a szerepkörödet.
+notification-admin-user_enable = Egy adminisztrátor engedélyezte a fiókodat.
+notification-admin-user_issue_token = Egy adminisztrátor új API tokent adott ki neked.
+notification-admin-user_revoke_token = Egy adminisztrátor visszavonta az API tokenedet.
+notification-all = Összes értesítés
+notification-read = Olvasottnak jelölés
+notification-read-all = Összes olvasottnak jelölése
+notification-index-title = Értesítések
+notification-preferences-title = Értesíts, ha
+notification-preference-mention = megemlítenek
+notification-preference-reply = válaszolnak az üzeneteimre
+notification-preference-edit = szerkesztik az üzeneteimet
+notification-preference-admin = moderátor vagy adminisztrátor módosít valamit
+notification-page = { $page }. oldal / { $pages }
+notification-previous = Előző
+notification-next = Következő
+
+audit-title = NAPLÓ
+audit-actor = Szereplő
+audit-action = Művelet
+audit-any = Bármely
+audit-from = Kezdete
+audit-to = Vége
+audit-export-csv = CSV exportálása
+audit-export-json = JSON exportálása
+audit-time = Időpont
+audit-target = Cél
+audit-ip = IP
+audit-user-agent = Böngésző
+audit-empty = Nincsenek események.
+
+error-back = Vissza
+error-unauthorized = Bejelentkezés szükséges.
+error-not-found = A kért erőforrás nem létezik.
+error-conflict = A kérés ütközik a meglévő adatokkal.
+error-internal = Valami hiba történt, kérjük, próbáld újra.
+
+toast-message-sent = A(z) #{ $id } üzenet elküldve.
+toast-message-deleted = A(z) #{ $id } üzenet törölve.
+toast-reply-sent = A(z) #{ $id } válasz elküldve.
+toast-notification-preferences = Értesítési beállítások mentve.
+toast-registered = Sikeres regisztráció.
+toast-channel-member-added = { $name } hozzáadva a csatornához.
+
+field-title = Cím
+field-content = Tartalom
+field-name = Név
+field-password = Jelszó
+field-participants = Résztvevők
+field-channel = Csatorna neve
+field-tags = Címkék, vesszővel elválasztva
+field-reaction = Reakció
+field-from = Kezdete
+field-to = Vége
+
+validation-blank = { $field }: nem állhat csak szóközökből.
+validation-min-length = { $field }: legalább { $min } karakter hosszúnak kell lennie.
+validation-max-length = { $field }: legfeljebb { $max } karakter hosszú lehet.
+validation-invalid = { $field }: érvénytelen érték.
+validation-taken = A név már foglalt.
+validation-unknown-user = Nincs ilyen felhasználó.
+validation-member = Már tag.
+validation-participants = Adj meg 1 és { $max } közötti számú további résztvevőt.
+validation-tags = Legfeljebb { $max } címkét adj meg, egyenként legfeljebb { $length } karakterrel.
+validation-unknown-participant = Nincs ilyen felhasználó: { $name }.
+validation-internal = Belső szerverhiba.
@@ -0,0 +1,20 @@
+CREATE TABLE audit_events (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    actor VARCHAR(255),
+    action VARCHAR(50) NOT NULL,
+    target VARCHAR(255),
+    ip VARCHAR(45),
+    user_agent VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    INDEX (actor),
+    INDEX (action),
+    INDEX (created_at)
+);
+
+CREATE TRIGGER audit_events_no_update BEFORE UPDATE ON audit_events
+FOR EACH ROW SIGNAL SQLSTATE '45000'
+SET MESSAGE_TEXT = 'audit_events is append-only';
+
+CREATE TRIGGER audit_events_no_delete BEFORE DELETE ON audit_events
+FOR EACH ROW SIGNAL SQLSTATE '45000'
+SET MESSAGE_TEXT = 'audit_events is append-only';
@@ -0,0 +1 @@
+ALTER TABLE users ADD api_token VARCHAR(97) NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE messages
+    DROP FOREIGN KEY messages_parent_id,
+    DROP COLUMN parent_id;
@@ -0,0 +1,4 @@
+ALTER TABLE messages
+    ADD parent_id INT NULL,
+    ADD CONSTRAINT messages_parent_id
+        FOREIGN KEY (parent_id) REFERENCES messages (id) ON DELETE CASCADE;
@@ -0,0 +1,6 @@
+ALTER TABLE messages
+    DROP FOREIGN KEY messages_channel_id,
+    DROP COLUMN channel_id;
+
+DROP TABLE channel_members;
+DROP TABLE channels;
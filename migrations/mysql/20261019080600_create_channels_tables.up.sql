@@ -0,0 +1,20 @@
+CREATE TABLE channels (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    name VARCHAR(50) NOT NULL UNIQUE,
+    private BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE channel_members (
+    channel_id INT NOT NULL,
+    user_name VARCHAR(50) NOT NULL,
+    PRIMARY KEY (channel_id, user_name),
+    FOREIGN KEY (channel_id) REFERENCES channels (id) ON DELETE CASCADE,
+    FOREIGN KEY (user_name) REFERENCES users (name) ON DELETE CASCADE
+);
+
+INSERT INTO channels (name, private) VALUES ('general', FALSE);
+
+ALTER TABLE messages
+    ADD channel_id INT NOT NULL DEFAULT 1,
+    ADD CONSTRAINT messages_channel_id
+        FOREIGN KEY (channel_id) REFERENCES channels (id) ON DELETE CASCADE;
@@ -0,0 +1,23 @@
+CREATE TABLE conversations (
+    id INT PRIMARY KEY AUTO_INCREMENT
+);
+
+CREATE TABLE conversation_participants (
+    conversation_id INT NOT NULL,
+    user_name VARCHAR(50) NOT NULL,
+    last_read INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (conversation_id, user_name),
+    FOREIGN KEY (conversation_id)
+        REFERENCES conversations (id) ON DELETE CASCADE,
+    FOREIGN KEY (user_name) REFERENCES users (name) ON DELETE CASCADE
+);
+
+CREATE TABLE direct_messages (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    conversation_id INT NOT NULL,
+    sender VARCHAR(50) NOT NULL,
+    content VARCHAR(1000) NOT NULL,
+    FOREIGN KEY (conversation_id)
+        REFERENCES conversations (id) ON DELETE CASCADE,
+    FOREIGN KEY (sender) REFERENCES users (name) ON DELETE CASCADE
+);
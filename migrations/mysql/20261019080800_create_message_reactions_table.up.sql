@@ -0,0 +1,8 @@
+CREATE TABLE message_reactions (
+    message_id INT NOT NULL,
+    user_name VARCHAR(50) NOT NULL,
+    reaction VARCHAR(20) NOT NULL,
+    PRIMARY KEY (message_id, user_name),
+    FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE,
+    FOREIGN KEY (user_name) REFERENCES users (name) ON DELETE CASCADE
+);
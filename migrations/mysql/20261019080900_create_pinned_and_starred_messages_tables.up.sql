@@ -0,0 +1,14 @@
+CREATE TABLE pinned_messages (
+    message_id INT PRIMARY KEY,
+    pinned_by VARCHAR(50) NOT NULL,
+    FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE,
+    FOREIGN KEY (pinned_by) REFERENCES users (name) ON DELETE CASCADE
+);
+
+CREATE TABLE starred_messages (
+    user_name VARCHAR(50) NOT NULL,
+    message_id INT NOT NULL,
+    PRIMARY KEY (user_name, message_id),
+    FOREIGN KEY (user_name) REFERENCES users (name) ON DELETE CASCADE,
+    FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE
+);
@@ -0,0 +1,20 @@
+CREATE TABLE message_mentions (
+    message_id INT NOT NULL,
+    user_name VARCHAR(50) NOT NULL,
+    PRIMARY KEY (message_id, user_name),
+    FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE,
+    FOREIGN KEY (user_name) REFERENCES users (name) ON DELETE CASCADE
+);
+
+CREATE TABLE notifications (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    user_name VARCHAR(50) NOT NULL,
+    kind VARCHAR(20) NOT NULL,
+    actor VARCHAR(50),
+    message_id INT,
+    seen BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    INDEX (user_name, seen),
+    FOREIGN KEY (user_name) REFERENCES users (name) ON DELETE CASCADE,
+    FOREIGN KEY (message_id) REFERENCES messages (id) ON DELETE CASCADE
+);
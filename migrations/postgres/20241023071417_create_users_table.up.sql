@@ -0,0 +1,4 @@
+CREATE TABLE users (
+    name VARCHAR(50) PRIMARY KEY,
+    password VARCHAR(97) NOT NULL
+);
@@ -0,0 +1,5 @@
+CREATE TABLE messages (
+    id SERIAL PRIMARY KEY,
+    title VARCHAR(100) NOT NULL,
+    content VARCHAR(1000) NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE users ADD role VARCHAR(20) NOT NULL DEFAULT 'user';
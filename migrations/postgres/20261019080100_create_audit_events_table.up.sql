@@ -0,0 +1,22 @@
+CREATE TABLE audit_events (
+    id SERIAL PRIMARY KEY,
+    actor VARCHAR(255),
+    action VARCHAR(50) NOT NULL,
+    target VARCHAR(255),
+    ip VARCHAR(45),
+    user_agent VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX audit_events_actor ON audit_events (actor);
+CREATE INDEX audit_events_action ON audit_events (action);
+CREATE INDEX audit_events_created_at ON audit_events (created_at);
+
+CREATE FUNCTION audit_events_append_only() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_events_append_only BEFORE UPDATE OR DELETE ON audit_events
+FOR EACH ROW EXECUTE FUNCTION audit_events_append_only();
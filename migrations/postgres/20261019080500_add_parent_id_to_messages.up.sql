@@ -0,0 +1,4 @@
+ALTER TABLE messages
+    ADD parent_id INT NULL REFERENCES messages (id) ON DELETE CASCADE;
+
+CREATE INDEX messages_parent_id ON messages (parent_id);
@@ -0,0 +1,4 @@
+ALTER TABLE messages DROP COLUMN channel_id;
+
+DROP TABLE channel_members;
+DROP TABLE channels;
@@ -0,0 +1,21 @@
+CREATE TABLE channels (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(50) NOT NULL UNIQUE,
+    private BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE channel_members (
+    channel_id INT NOT NULL REFERENCES channels (id) ON DELETE CASCADE,
+    user_name VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+    PRIMARY KEY (channel_id, user_name)
+);
+
+CREATE INDEX channel_members_user_name ON channel_members (user_name);
+
+INSERT INTO channels (name, private) VALUES ('general', FALSE);
+
+ALTER TABLE messages
+    ADD channel_id INT NOT NULL DEFAULT 1
+        REFERENCES channels (id) ON DELETE CASCADE;
+
+CREATE INDEX messages_channel_id ON messages (channel_id);
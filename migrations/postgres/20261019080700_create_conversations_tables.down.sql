@@ -0,0 +1,3 @@
+DROP TABLE direct_messages;
+DROP TABLE conversation_participants;
+DROP TABLE conversations;
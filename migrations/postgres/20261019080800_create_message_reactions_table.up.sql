@@ -0,0 +1,6 @@
+CREATE TABLE message_reactions (
+    message_id INT NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+    user_name VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+    reaction VARCHAR(20) NOT NULL,
+    PRIMARY KEY (message_id, user_name)
+);
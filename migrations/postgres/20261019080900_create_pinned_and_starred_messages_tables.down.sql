@@ -0,0 +1,2 @@
+DROP TABLE starred_messages;
+DROP TABLE pinned_messages;
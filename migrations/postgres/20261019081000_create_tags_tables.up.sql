@@ -0,0 +1,12 @@
+CREATE TABLE tags (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(30) NOT NULL UNIQUE
+);
+
+CREATE TABLE message_tags (
+    message_id INT NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+    tag_id INT NOT NULL REFERENCES tags (id) ON DELETE CASCADE,
+    PRIMARY KEY (message_id, tag_id)
+);
+
+CREATE INDEX message_tags_tag_id ON message_tags (tag_id);
@@ -0,0 +1,5 @@
+DROP TABLE notification_preferences;
+
+ALTER TABLE notifications DROP COLUMN detail;
+
+ALTER TABLE messages DROP COLUMN author;
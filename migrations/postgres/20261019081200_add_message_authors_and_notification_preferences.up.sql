@@ -0,0 +1,10 @@
+ALTER TABLE messages ADD author VARCHAR(50) NULL;
+
+ALTER TABLE notifications ADD detail VARCHAR(50) NULL;
+
+CREATE TABLE notification_preferences (
+    user_name VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    PRIMARY KEY (user_name, kind)
+);
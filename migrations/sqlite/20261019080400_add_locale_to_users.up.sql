@@ -0,0 +1 @@
+ALTER TABLE users ADD locale VARCHAR(16) NULL;
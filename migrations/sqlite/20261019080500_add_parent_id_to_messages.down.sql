@@ -0,0 +1,14 @@
+DROP INDEX messages_parent_id;
+
+CREATE TABLE messages_without_parent_id (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title VARCHAR(100) NOT NULL,
+    content VARCHAR(1000) NOT NULL
+);
+
+INSERT INTO messages_without_parent_id (id, title, content)
+SELECT id, title, content FROM messages;
+
+DROP TABLE messages;
+
+ALTER TABLE messages_without_parent_id RENAME TO messages;
@@ -0,0 +1,19 @@
+CREATE TABLE messages_without_channel_id (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title VARCHAR(100) NOT NULL,
+    content VARCHAR(1000) NOT NULL,
+    parent_id INTEGER NULL
+        REFERENCES messages_without_channel_id (id) ON DELETE CASCADE
+);
+
+INSERT INTO messages_without_channel_id (id, title, content, parent_id)
+SELECT id, title, content, parent_id FROM messages;
+
+DROP TABLE messages;
+
+ALTER TABLE messages_without_channel_id RENAME TO messages;
+
+CREATE INDEX messages_parent_id ON messages (parent_id);
+
+DROP TABLE channel_members;
+DROP TABLE channels;
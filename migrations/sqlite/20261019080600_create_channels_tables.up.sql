@@ -0,0 +1,23 @@
+CREATE TABLE channels (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name VARCHAR(50) NOT NULL UNIQUE,
+    private BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE channel_members (
+    channel_id INTEGER NOT NULL REFERENCES channels (id) ON DELETE CASCADE,
+    user_name VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+    PRIMARY KEY (channel_id, user_name)
+);
+
+CREATE INDEX channel_members_user_name ON channel_members (user_name);
+
+INSERT INTO channels (name, private) VALUES ('general', FALSE);
+
+-- SQLite cannot add a foreign key column with a non-NULL default.
+ALTER TABLE messages
+    ADD channel_id INTEGER NULL REFERENCES channels (id) ON DELETE CASCADE;
+
+UPDATE messages SET channel_id = 1;
+
+CREATE INDEX messages_channel_id ON messages (channel_id);
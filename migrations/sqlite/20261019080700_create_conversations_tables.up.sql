@@ -0,0 +1,25 @@
+CREATE TABLE conversations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT
+);
+
+CREATE TABLE conversation_participants (
+    conversation_id INTEGER NOT NULL
+        REFERENCES conversations (id) ON DELETE CASCADE,
+    user_name VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+    last_read INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (conversation_id, user_name)
+);
+
+CREATE INDEX conversation_participants_user_name
+    ON conversation_participants (user_name);
+
+CREATE TABLE direct_messages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    conversation_id INTEGER NOT NULL
+        REFERENCES conversations (id) ON DELETE CASCADE,
+    sender VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+    content VARCHAR(1000) NOT NULL
+);
+
+CREATE INDEX direct_messages_conversation_id
+    ON direct_messages (conversation_id);
@@ -0,0 +1,10 @@
+CREATE TABLE pinned_messages (
+    message_id INTEGER PRIMARY KEY REFERENCES messages (id) ON DELETE CASCADE,
+    pinned_by VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE
+);
+
+CREATE TABLE starred_messages (
+    user_name VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+    message_id INTEGER NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+    PRIMARY KEY (user_name, message_id)
+);
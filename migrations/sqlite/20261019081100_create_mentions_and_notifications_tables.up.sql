@@ -0,0 +1,17 @@
+CREATE TABLE message_mentions (
+    message_id INTEGER NOT NULL REFERENCES messages (id) ON DELETE CASCADE,
+    user_name VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+    PRIMARY KEY (message_id, user_name)
+);
+
+CREATE TABLE notifications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_name VARCHAR(50) NOT NULL REFERENCES users (name) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL,
+    actor VARCHAR(50),
+    message_id INTEGER REFERENCES messages (id) ON DELETE CASCADE,
+    seen BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX notifications_user_name_seen ON notifications (user_name, seen);
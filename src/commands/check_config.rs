@@ -0,0 +1,12 @@
+use std::error::Error;
+
+use tracing::instrument;
+
+use crate::config::Config;
+
+#[instrument(level = "debug")]
+pub fn check_config(config: &Config) -> Result<(), Box<dyn Error>> {
+    println!("{config:#?}");
+    println!("Configuration is valid.");
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use std::error::Error;
+
+use tracing::instrument;
+
+use crate::{
+    config::Config, database::DatabasePool, services::migrator::MigratorService,
+};
+
+#[instrument(level = "debug")]
+pub async fn migrate(config: Config) -> Result<(), Box<dyn Error>> {
+    let database = DatabasePool::connect(&config.database_url).await?;
+    MigratorService::migrate(&database, true).await?;
+    Ok(())
+}
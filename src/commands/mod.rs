@@ -0,0 +1,50 @@
+mod check_config;
+mod migrate;
+mod serve;
+mod user;
+
+use std::error::Error;
+
+use clap::{Parser, Subcommand};
+use tracing::instrument;
+
+use crate::config::Config;
+
+use self::user::UserCommand;
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Serve the web application (default).
+    Serve,
+    /// Apply pending database migrations.
+    Migrate,
+    /// Manage user accounts.
+    #[command(subcommand)]
+    User(UserCommand),
+    /// Validate the configuration and exit.
+    CheckConfig,
+}
+
+impl Cli {
+    #[cfg(feature = "otel")]
+    pub fn serves(&self) -> bool {
+        matches!(self.command, None | Some(Command::Serve))
+    }
+
+    #[instrument(level = "debug")]
+    pub async fn run(self, config: Config) -> Result<(), Box<dyn Error>> {
+        match self.command.unwrap_or(Command::Serve) {
+            Command::Serve => serve::serve(config).await,
+            Command::Migrate => migrate::migrate(config).await,
+            Command::User(command) => command.run(config).await,
+            Command::CheckConfig => check_config::check_config(&config),
+        }
+    }
+}
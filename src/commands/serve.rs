@@ -0,0 +1,118 @@
+use std::{error::Error, net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::Request,
+    middleware::{from_fn, from_fn_with_state},
+    serve as axum_serve,
+};
+use axum_csrf::{CsrfConfig, CsrfLayer};
+use tokio::{net::TcpListener, signal::ctrl_c, spawn};
+use tower_http::{
+    request_id::{
+        MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer,
+    },
+    services::ServeDir,
+    trace::TraceLayer,
+};
+#[cfg(feature = "otel")]
+use tracing::warn;
+use tracing::{error, field::Empty, info, instrument, span, Level};
+#[cfg(feature = "otel")]
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+#[cfg(feature = "otel")]
+use crate::services::telemetry::TelemetryService;
+use crate::{
+    config::Config,
+    routes::{health, metrics, routes},
+    services::{
+        authenticator::AuthenticatorService,
+        error::error_service,
+        i18n::{locale_service, I18nService},
+        integrity::integrity_service,
+        metrics::{metrics_service, MetricsService},
+        migrator::MigratorService,
+        span::{span_service, span_user_service},
+        state::StateService,
+    },
+};
+
+#[instrument(level = "debug")]
+pub async fn serve(config: Config) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind(config.app_address).await?;
+    info!("{listener:?}");
+    let state = Arc::new(
+        StateService::new(
+            config,
+            MetricsService::install()?,
+            I18nService::new()?,
+        )
+        .await?,
+    );
+    info!("{state:?}");
+    MigratorService::migrate(state.database(), state.config().database_migrate)
+        .await?;
+    let mut router = routes()
+        .layer(from_fn(error_service))
+        .layer(from_fn_with_state(state.clone(), locale_service))
+        .layer(from_fn(span_user_service))
+        .layer(
+            AuthenticatorService::new(state.database().clone(), state.config())
+                .await?,
+        )
+        .layer(from_fn(integrity_service))
+        .layer(CsrfLayer::new(CsrfConfig::default()))
+        .layer(from_fn(metrics_service))
+        .layer(from_fn(span_service))
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(TraceLayer::new_for_http().make_span_with(
+            |request: &Request| {
+                let span = span! {
+                    Level::INFO,
+                    "request",
+                    method = %request.method(),
+                    route = %request.uri(),
+                    request_id = request
+                        .extensions()
+                        .get::<RequestId>()
+                        .and_then(|id| id.header_value().to_str().ok()),
+                    user = Empty,
+                };
+                #[cfg(feature = "otel")]
+                if !span.is_disabled() {
+                    if let Err(error) = span.set_parent(
+                        TelemetryService::context(request.headers()),
+                    ) {
+                        warn!("{error}");
+                    }
+                }
+                span
+            },
+        ))
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        .merge(health::routes());
+    if let Some(address) = state.config().metrics_address {
+        let listener = TcpListener::bind(address).await?;
+        info!("{listener:?}");
+        let router = metrics::routes().with_state(state.clone());
+        spawn(async move {
+            if let Err(error) = axum_serve(listener, router).await {
+                error!("{error}");
+            }
+        });
+    } else if state.config().metrics_token.is_some() {
+        router = router.merge(metrics::routes());
+    } else {
+        info!("metrics endpoint disabled");
+    }
+    axum_serve(
+        listener,
+        router
+            .with_state(state)
+            .nest_service("/assets", ServeDir::new("./assets/"))
+            .into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async { ctrl_c().await.unwrap() })
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use std::{
+    error::Error,
+    io::{stderr, stdin, Write},
+    sync::Arc,
+};
+
+use clap::Subcommand;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    config::Config,
+    database::DatabasePool,
+    models::{
+        audit::AuditEventModel, notification::NotificationModel,
+        user::UserModel,
+    },
+    services::i18n::{I18nService, LocaleService},
+    validation::validate,
+};
+
+#[derive(Debug, Subcommand)]
+pub enum UserCommand {
+    /// Create a user, reading the password from standard input.
+    Create {
+        name: String,
+        #[arg(long, default_value = "user", value_parser = UserModel::ROLES)]
+        role: String,
+    },
+    /// Change the password of a user, reading it from standard input.
+    SetPassword { name: String },
+    /// Change the role of a user.
+    SetRole {
+        name: String,
+        #[arg(value_parser = UserModel::ROLES)]
+        role: String,
+    },
+    /// Prevent a user from logging in and end their sessions.
+    Disable { name: String },
+    /// Allow a disabled user to log in again.
+    Enable { name: String },
+    /// Issue an API token for a user, replacing the previous one.
+    Token { name: String },
+    /// Revoke the API token of a user.
+    RevokeToken { name: String },
+}
+
+impl UserCommand {
+    #[instrument(level = "debug")]
+    pub async fn run(self, config: Config) -> Result<(), Box<dyn Error>> {
+        let database = DatabasePool::connect(&config.database_url).await?;
+        let locale =
+            LocaleService::new(Arc::new(I18nService::new()?), None, None);
+        let mut token = None;
+        let (name, action, query) = match self {
+            Self::Create { name, role } => {
+                let user = UserModel {
+                    password: password()?,
+                    role,
+                    disabled: false,
+                    locale: None,
+                    name,
+                };
+                if let Err(errors) =
+                    UserModel::validate(&database, &config, &locale, &user)
+                        .await
+                {
+                    return Err(errors
+                        .messages()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .into());
+                }
+                UserModel::create(&database, &user.name, &user.password)
+                    .await?;
+                let query =
+                    UserModel::set_role(&database, &user.name, &user.role)
+                        .await?;
+                (user.name, AuditEventModel::USER_CREATE, query)
+            }
+            Self::SetPassword { name } => {
+                let user = UserModel {
+                    name: name.clone(),
+                    password: password()?,
+                    role: String::new(),
+                    disabled: false,
+                    locale: None,
+                };
+                if let Some(error) = validate(&user, &config, &locale)
+                    .err()
+                    .as_ref()
+                    .and_then(|errors| errors.get("password"))
+                {
+                    return Err(error.into());
+                }
+                let query =
+                    UserModel::set_password(&database, &name, &user.password)
+                        .await?;
+                (name, AuditEventModel::USER_SET_PASSWORD, query)
+            }
+            Self::SetRole { name, role } => {
+                let query =
+                    UserModel::set_role(&database, &name, &role).await?;
+                (name, AuditEventModel::USER_SET_ROLE, query)
+            }
+            Self::Disable { name } => {
+                let query =
+                    UserModel::set_disabled(&database, &name, true).await?;
+                (name, AuditEventModel::USER_DISABLE, query)
+            }
+            Self::Enable { name } => {
+                let query =
+                    UserModel::set_disabled(&database, &name, false).await?;
+                (name, AuditEventModel::USER_ENABLE, query)
+            }
+            Self::Token { name } => {
+                let secret = Uuid::new_v4().simple().to_string();
+                let query =
+                    UserModel::set_api_token(&database, &name, Some(&secret))
+                        .await?;
+                token = Some(format!("{name}:{secret}"));
+                (name, AuditEventModel::USER_ISSUE_TOKEN, query)
+            }
+            Self::RevokeToken { name } => {
+                let query =
+                    UserModel::set_api_token(&database, &name, None).await?;
+                (name, AuditEventModel::USER_REVOKE_TOKEN, query)
+            }
+        };
+        if query.rows_affected() == 0
+            && UserModel::find(&database, &name).await?.is_none()
+        {
+            return Err(format!("User {name} not found.").into());
+        }
+        AuditEventModel::create(
+            &database,
+            None,
+            action,
+            Some(&name),
+            None,
+            None,
+        )
+        .await?;
+        if ![AuditEventModel::USER_CREATE, AuditEventModel::USER_DISABLE]
+            .contains(&action)
+        {
+            NotificationModel::send(
+                &database,
+                &name,
+                NotificationModel::ADMIN,
+                None,
+                Some(action),
+                None,
+            )
+            .await?;
+        }
+        println!("User {name} updated.");
+        if let Some(token) = token {
+            println!("{token}");
+        }
+        Ok(())
+    }
+}
+
+fn password() -> Result<String, Box<dyn Error>> {
+    eprint!("Password: ");
+    stderr().flush()?;
+    let mut password = String::new();
+    stdin().read_line(&mut password)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_owned())
+}
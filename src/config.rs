@@ -0,0 +1,419 @@
+use std::{
+    env::{var, VarError},
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    fs::read_to_string,
+    net::SocketAddr,
+    path::Path,
+    str::FromStr,
+};
+
+use time::Duration;
+use toml::{Table, Value};
+use tracing::instrument;
+
+use crate::models::{
+    message::{CONTENT_LENGTH, TITLE_LENGTH},
+    user::NAME_LENGTH,
+};
+
+const PATH: &str = "rustweb.toml";
+
+const KEYS: [&str; 13] = [
+    "app.address",
+    "log.format",
+    "database.url",
+    "database.migrate",
+    "session.expiry",
+    "session.secure",
+    "messages.capacity",
+    "messages.title_max",
+    "messages.content_max",
+    "users.name_max",
+    "users.password_min",
+    "metrics.address",
+    "metrics.token",
+];
+
+#[derive(Clone)]
+pub struct Config {
+    pub app_address: SocketAddr,
+    pub log_format: LogFormat,
+    pub database_url: String,
+    pub database_migrate: bool,
+    pub session_expiry: Duration,
+    pub session_secure: bool,
+    pub messages_capacity: usize,
+    pub messages_title_max: usize,
+    pub messages_content_max: usize,
+    pub users_name_max: usize,
+    pub users_password_min: usize,
+    pub metrics_address: Option<SocketAddr>,
+    pub metrics_token: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+pub struct ConfigError(Vec<String>);
+
+struct ConfigSource {
+    table: Table,
+    errors: Vec<String>,
+    vars: fn(&str) -> Result<String, VarError>,
+}
+
+impl Debug for Config {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Config")
+            .field("app_address", &self.app_address)
+            .field("log_format", &self.log_format)
+            .field("database_url", &"********")
+            .field("database_migrate", &self.database_migrate)
+            .field("session_expiry", &self.session_expiry)
+            .field("session_secure", &self.session_secure)
+            .field("messages_capacity", &self.messages_capacity)
+            .field("messages_title_max", &self.messages_title_max)
+            .field("messages_content_max", &self.messages_content_max)
+            .field("users_name_max", &self.users_name_max)
+            .field("users_password_min", &self.users_password_min)
+            .field("metrics_address", &self.metrics_address)
+            .field(
+                "metrics_token",
+                &self.metrics_token.as_ref().map(|_| "********"),
+            )
+            .finish()
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err("expected text or json".to_owned()),
+        }
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "invalid configuration:")?;
+        for error in &self.0 {
+            write!(formatter, "\n  - {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for ConfigError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, formatter)
+    }
+}
+
+impl Error for ConfigError {}
+
+impl Config {
+    #[instrument(level = "debug")]
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut source = ConfigSource::new(|key| var(key));
+        let app_address = source.get("app.address", None);
+        let log_format = source.get("log.format", Some(LogFormat::Text));
+        let database_url = source.get("database.url", None);
+        let database_migrate = source.get("database.migrate", Some(false));
+        let session_expiry = source.positive("session.expiry", 86400);
+        let session_secure = source.get("session.secure", Some(false));
+        let messages_capacity = source.positive("messages.capacity", 255);
+        let messages_title_max =
+            source.bounded("messages.title_max", TITLE_LENGTH);
+        let messages_content_max =
+            source.bounded("messages.content_max", CONTENT_LENGTH);
+        let users_name_max = source.bounded("users.name_max", NAME_LENGTH);
+        let users_password_min = source.positive("users.password_min", 8);
+        let metrics_address = source.optional("metrics.address");
+        let metrics_token = source.optional("metrics.token");
+        match (
+            app_address,
+            log_format,
+            database_url,
+            database_migrate,
+            session_expiry,
+            session_secure,
+            messages_capacity,
+            messages_title_max,
+            messages_content_max,
+            users_name_max,
+            users_password_min,
+        ) {
+            (
+                Some(app_address),
+                Some(log_format),
+                Some(database_url),
+                Some(database_migrate),
+                Some(session_expiry),
+                Some(session_secure),
+                Some(messages_capacity),
+                Some(messages_title_max),
+                Some(messages_content_max),
+                Some(users_name_max),
+                Some(users_password_min),
+            ) if source.errors.is_empty() => Ok(Self {
+                app_address,
+                log_format,
+                database_url,
+                database_migrate,
+                session_expiry: Duration::seconds(session_expiry),
+                session_secure,
+                messages_capacity,
+                messages_title_max,
+                messages_content_max,
+                users_name_max,
+                users_password_min,
+                metrics_address,
+                metrics_token,
+            }),
+            _ => Err(ConfigError(source.errors)),
+        }
+    }
+}
+
+impl ConfigSource {
+    fn new(vars: fn(&str) -> Result<String, VarError>) -> Self {
+        let path = match vars("APP_CONFIG") {
+            Ok(path) => Some(path),
+            Err(..) if Path::new(PATH).exists() => Some(PATH.to_owned()),
+            Err(..) => None,
+        };
+        let Some(path) = path else {
+            return Self {
+                table: Table::new(),
+                errors: Vec::new(),
+                vars,
+            };
+        };
+        match read_to_string(&path) {
+            Ok(file) => Self::parse(&path, &file, vars),
+            Err(error) => Self {
+                table: Table::new(),
+                errors: vec![format!("{path}: {error}")],
+                vars,
+            },
+        }
+    }
+
+    fn parse(
+        path: &str,
+        file: &str,
+        vars: fn(&str) -> Result<String, VarError>,
+    ) -> Self {
+        let mut errors = Vec::new();
+        let table = file.parse::<Table>().unwrap_or_else(|error| {
+            errors.push(format!("{path}: {error}"));
+            Table::new()
+        });
+        for (section, keys) in &table {
+            let Value::Table(keys) = keys else {
+                errors.push(format!("{section}: expected a table"));
+                continue;
+            };
+            for key in keys.keys() {
+                let key = format!("{section}.{key}");
+                if !KEYS.contains(&key.as_str()) {
+                    errors.push(format!("{key}: unknown key"));
+                }
+            }
+        }
+        Self {
+            table,
+            errors,
+            vars,
+        }
+    }
+
+    fn get<T: FromStr>(&mut self, key: &str, default: Option<T>) -> Option<T>
+    where
+        T::Err: Display,
+    {
+        if !matches!((self.vars)(&env(key)), Err(VarError::NotPresent))
+            || self.lookup(key).is_some()
+        {
+            self.optional(key)
+        } else if default.is_some() {
+            default
+        } else {
+            self.errors.push(format!("{key} ({}): missing", env(key)));
+            None
+        }
+    }
+
+    fn optional<T: FromStr>(&mut self, key: &str) -> Option<T>
+    where
+        T::Err: Display,
+    {
+        let env = env(key);
+        let value = match (self.vars)(&env) {
+            Ok(value) => value,
+            Err(VarError::NotUnicode(..)) => {
+                self.errors.push(format!("{key} ({env}): invalid unicode"));
+                return None;
+            }
+            Err(VarError::NotPresent) => match self.lookup(key)? {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            },
+        };
+        match value.parse() {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.errors.push(format!("{key} ({env}): {error}"));
+                None
+            }
+        }
+    }
+
+    fn lookup(&self, key: &str) -> Option<&Value> {
+        let (section, key) = key.split_once('.')?;
+        self.table.get(section)?.get(key)
+    }
+
+    fn positive<T: FromStr + Default + PartialOrd>(
+        &mut self,
+        key: &str,
+        default: T,
+    ) -> Option<T>
+    where
+        T::Err: Display,
+    {
+        let value = self.get(key, Some(default))?;
+        if value <= T::default() {
+            self.errors.push(format!("{key}: must be positive"));
+            return None;
+        }
+        Some(value)
+    }
+
+    fn bounded(&mut self, key: &str, max: usize) -> Option<usize> {
+        let value = self.positive(key, max)?;
+        if max < value {
+            self.errors.push(format!("{key}: must not exceed {max}"));
+            return None;
+        }
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+impl Config {
+    pub fn test() -> Self {
+        Self {
+            app_address: SocketAddr::from(([127, 0, 0, 1], 0)),
+            log_format: LogFormat::Text,
+            database_url: String::new(),
+            database_migrate: false,
+            session_expiry: Duration::seconds(86400),
+            session_secure: false,
+            messages_capacity: 255,
+            messages_title_max: TITLE_LENGTH,
+            messages_content_max: CONTENT_LENGTH,
+            users_name_max: NAME_LENGTH,
+            users_password_min: 8,
+            metrics_address: None,
+            metrics_token: None,
+        }
+    }
+}
+
+fn env(key: &str) -> String {
+    key.replace('.', "_").to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(file: &str) -> ConfigSource {
+        ConfigSource::parse("test.toml", file, |_| Err(VarError::NotPresent))
+    }
+
+    #[test]
+    fn collects_all_errors() {
+        let mut source = source(concat!(
+            "cache = 1\n",
+            "[messages]\n",
+            "capacity = \"many\"\n",
+            "title_max = 1000\n",
+            "[users]\n",
+            "name_max = 51\n",
+            "colour = \"red\"\n",
+        ));
+        assert_eq!(source.positive::<usize>("messages.capacity", 255), None);
+        assert_eq!(source.bounded("messages.title_max", TITLE_LENGTH), None);
+        assert_eq!(source.bounded("users.name_max", NAME_LENGTH), None);
+        assert_eq!(source.positive::<usize>("users.password_min", 8), Some(8));
+        assert_eq!(
+            source.errors,
+            [
+                "cache: expected a table",
+                "users.colour: unknown key",
+                "messages.capacity (MESSAGES_CAPACITY): invalid digit found \
+                 in string",
+                "messages.title_max: must not exceed 100",
+                "users.name_max: must not exceed 50",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_toml() {
+        let source = source("[messages");
+        assert_eq!(source.errors.len(), 1);
+        assert!(source.errors[0].starts_with("test.toml: "));
+    }
+
+    #[test]
+    fn requires_positive_values() {
+        let mut source = source(
+            "[session]\nexpiry = -1\n[users]\nname_max = 0\npassword_min = 1\n",
+        );
+        assert_eq!(source.positive::<i64>("session.expiry", 86400), None);
+        assert_eq!(source.positive::<usize>("users.name_max", 50), None);
+        assert_eq!(source.positive::<usize>("users.password_min", 8), Some(1));
+        assert_eq!(source.errors.len(), 2);
+    }
+
+    #[test]
+    fn overrides_file_with_environment() {
+        let mut source = ConfigSource::parse(
+            "test.toml",
+            "[users]\nname_max = 20\npassword_min = 10\n",
+            |key| match key {
+                "USERS_NAME_MAX" => Ok("30".to_owned()),
+                "APP_ADDRESS" => Ok("127.0.0.1:8080".to_owned()),
+                _ => Err(VarError::NotPresent),
+            },
+        );
+        assert_eq!(source.bounded("users.name_max", NAME_LENGTH), Some(30));
+        assert_eq!(source.positive::<usize>("users.password_min", 8), Some(10));
+        assert_eq!(
+            source.get("app.address", None),
+            Some(SocketAddr::from(([127, 0, 0, 1], 8080)))
+        );
+        assert_eq!(source.get::<String>("database.url", None), None);
+        assert_eq!(source.errors, ["database.url (DATABASE_URL): missing"]);
+    }
+
+    #[test]
+    fn parses_log_format() {
+        assert_eq!("text".parse(), Ok(LogFormat::Text));
+        assert_eq!("json".parse(), Ok(LogFormat::Json));
+        assert!("JSON".parse::<LogFormat>().is_err());
+        assert!("".parse::<LogFormat>().is_err());
+    }
+}
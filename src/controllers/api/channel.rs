@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::IntoResponse, Json};
+use tracing::instrument;
+
+use crate::{
+    error::AppError,
+    models::channel::ChannelModel,
+    services::{api::ApiService, error::ErrorBody, state::StateService},
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/channels",
+    operation_id = "list_channels",
+    responses(
+        (
+            status = 200,
+            description = "The channels visible to the caller",
+            body = Vec<ChannelModel>,
+        ),
+        (status = 401, description = "Not authenticated", body = ErrorBody),
+    ),
+)]
+#[instrument(level = "debug")]
+pub async fn index(
+    State(state): State<Arc<StateService>>,
+    api: ApiService,
+) -> Result<impl IntoResponse, AppError> {
+    let channels =
+        ChannelModel::visible(state.database(), &api.user().name).await?;
+    Ok(Json(channels))
+}
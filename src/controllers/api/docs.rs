@@ -0,0 +1,100 @@
+use askama::Template;
+use axum::{
+    response::{Html, IntoResponse},
+    Json,
+};
+use tracing::instrument;
+use utoipa::{
+    openapi::{
+        security::{ApiKey, ApiKeyValue, Http, HttpAuthScheme, SecurityScheme},
+        OpenApi as OpenApiDocument,
+    },
+    Modify, OpenApi,
+};
+
+use crate::{
+    error::AppError,
+    models::{channel::ChannelModel, message::MessageModel},
+    services::error::ErrorBody,
+    templates::docs::DocsTemplate,
+    validation::FieldErrors,
+};
+
+use super::{
+    channel,
+    message::{self, MessagePage},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "rustweb"),
+    paths(
+        channel::index,
+        message::index,
+        message::show,
+        message::create,
+        message::update,
+        message::destroy,
+    ),
+    components(schemas(
+        ChannelModel,
+        MessageModel,
+        FieldErrors,
+        MessagePage,
+        ErrorBody,
+    )),
+    modifiers(&ApiDocSecurity),
+    security(("token" = []), ("session" = [])),
+)]
+pub struct ApiDoc;
+
+struct ApiDocSecurity;
+
+impl Modify for ApiDocSecurity {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        let components =
+            openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "token",
+            SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+        );
+        components.add_security_scheme(
+            "session",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new("id"))),
+        );
+    }
+}
+
+#[instrument(level = "debug")]
+pub async fn openapi() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+#[instrument(level = "debug")]
+pub async fn docs() -> Result<impl IntoResponse, AppError> {
+    Ok(Html(DocsTemplate::new("/api/openapi.json").render()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn operation_ids_are_unique() {
+        let document = ApiDoc::openapi();
+        let ids = document
+            .paths
+            .paths
+            .values()
+            .flat_map(|path| [&path.get, &path.post, &path.put, &path.delete])
+            .flatten()
+            .map(|operation| operation.operation_id.clone().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 6);
+        assert_eq!(ids.iter().collect::<HashSet<_>>().len(), ids.len());
+        assert!(ids.contains(&"list_channels".to_owned()));
+        assert!(ids.contains(&"list_messages".to_owned()));
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header::LOCATION, StatusCode},
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    error::AppError,
+    models::{channel::ChannelModel, message::MessageModel},
+    services::{
+        api::ApiService, error::ErrorBody, i18n::LocaleService,
+        message::MessageService, state::StateService,
+    },
+    validation::{validate, FieldErrors},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct MessagePageQuery {
+    #[param(default = 1)]
+    channel: Option<i32>,
+    #[param(minimum = 1, default = 1)]
+    page: Option<i64>,
+    #[param(minimum = 1, maximum = 100, default = 20)]
+    per_page: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessagePage {
+    messages: Vec<MessageModel>,
+    page: i64,
+    per_page: i64,
+    total: i64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/messages",
+    operation_id = "list_messages",
+    params(MessagePageQuery),
+    responses(
+        (status = 200, description = "A page of messages", body = MessagePage),
+        (status = 401, description = "Not authenticated", body = ErrorBody),
+    ),
+)]
+#[instrument(level = "debug")]
+pub async fn index(
+    State(state): State<Arc<StateService>>,
+    api: ApiService,
+    Query(query): Query<MessagePageQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let channel_id = MessageService::channel(
+        &state,
+        &api.user().name,
+        query.channel.unwrap_or(ChannelModel::GENERAL),
+    )
+    .await?;
+    let page = query.page.unwrap_or(1).max(1);
+    let per_page = query.per_page.unwrap_or(20).clamp(1, 100);
+    let messages = MessageModel::page(
+        state.database(),
+        channel_id,
+        per_page,
+        (page - 1).saturating_mul(per_page),
+    )
+    .await?;
+    let total = MessageModel::count(state.database(), channel_id).await?;
+    Ok(Json(MessagePage {
+        messages,
+        page,
+        per_page,
+        total,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/messages/{id}",
+    operation_id = "show_message",
+    params(("id" = i32, Path, description = "Message id")),
+    responses(
+        (status = 200, description = "The message", body = MessageModel),
+        (status = 401, description = "Not authenticated", body = ErrorBody),
+        (status = 404, description = "No such message", body = ErrorBody),
+    ),
+)]
+#[instrument(level = "debug")]
+pub async fn show(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    api: ApiService,
+) -> Result<impl IntoResponse, AppError> {
+    let message = MessageService::message(&state, &api.user().name, id).await?;
+    Ok(Json(message))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/messages",
+    operation_id = "create_message",
+    request_body = MessageModel,
+    responses(
+        (
+            status = 201,
+            description = "The created message",
+            body = MessageModel,
+            headers(("Location" = String, description = "Message URL")),
+        ),
+        (status = 401, description = "Not authenticated", body = ErrorBody),
+        (
+            status = 404,
+            description = "No such channel or parent",
+            body = ErrorBody,
+        ),
+        (status = 422, description = "Invalid message", body = FieldErrors),
+    ),
+)]
+#[instrument(level = "debug")]
+pub async fn create(
+    State(state): State<Arc<StateService>>,
+    Extension(locale): Extension<LocaleService>,
+    api: ApiService,
+    messages: MessageService,
+    Json(message): Json<MessageModel>,
+) -> Result<impl IntoResponse, AppError> {
+    let message = message.sanitize();
+    if let Err(errors) = validate(&message, state.config(), &locale) {
+        return Ok(
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        );
+    }
+    let message = messages.create(&api.user().name, message).await?;
+    let id = message.id.ok_or(AppError::NotFound)?;
+    Ok((
+        StatusCode::CREATED,
+        [(LOCATION, format!("/api/v1/messages/{id}"))],
+        Json(message),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/messages/{id}",
+    operation_id = "update_message",
+    params(("id" = i32, Path, description = "Message id")),
+    request_body = MessageModel,
+    responses(
+        (status = 200, description = "The updated message", body = MessageModel),
+        (status = 401, description = "Not authenticated", body = ErrorBody),
+        (status = 404, description = "No such message", body = ErrorBody),
+        (status = 422, description = "Invalid message", body = FieldErrors),
+    ),
+)]
+#[instrument(level = "debug")]
+pub async fn update(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    Extension(locale): Extension<LocaleService>,
+    api: ApiService,
+    messages: MessageService,
+    Json(message): Json<MessageModel>,
+) -> Result<impl IntoResponse, AppError> {
+    let message = message.sanitize();
+    if let Err(errors) = validate(&message, state.config(), &locale) {
+        return Ok(
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        );
+    }
+    let message = messages.update(&api.user().name, id, message).await?;
+    Ok(Json(message).into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/messages/{id}",
+    operation_id = "delete_message",
+    params(("id" = i32, Path, description = "Message id")),
+    responses(
+        (status = 204, description = "The message was deleted"),
+        (status = 401, description = "Not authenticated", body = ErrorBody),
+        (status = 404, description = "No such message", body = ErrorBody),
+    ),
+)]
+#[instrument(level = "debug")]
+pub async fn destroy(
+    Path(id): Path<i32>,
+    api: ApiService,
+    messages: MessageService,
+) -> Result<impl IntoResponse, AppError> {
+    messages.delete(&api.user().name, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,3 @@
+pub mod channel;
+pub mod docs;
+pub mod message;
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Query, State},
+    http::{header::CONTENT_DISPOSITION, header::CONTENT_TYPE, StatusCode},
+    response::{Html, IntoResponse},
+    Extension, Json,
+};
+use axum_csrf::CsrfToken;
+use axum_login::AuthSession;
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{
+    error::AppError,
+    models::audit::{AuditEventFilter, AuditEventModel},
+    services::{
+        authenticator::AuthenticatorService, i18n::LocaleService,
+        state::StateService,
+    },
+    templates::{
+        audit::{AuditEventsTemplate, AuditTemplate},
+        toast::ToastTemplate,
+    },
+    validation::validate,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditExportQuery {
+    format: AuditExportFormat,
+    #[serde(flatten)]
+    filter: AuditEventFilter,
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn index(
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<impl IntoResponse, AppError> {
+    let Some(user) = authenticator.user else {
+        return Ok((StatusCode::SEE_OTHER, csrf, [("HX-Location", "/")])
+            .into_response());
+    };
+    let audit = AuditTemplate::new(&token, &locale, &user).render()?;
+    Ok(
+        (StatusCode::OK, [("HX-Retarget", "body")], csrf, Html(audit))
+            .into_response(),
+    )
+}
+
+#[instrument(level = "debug", skip(csrf))]
+pub async fn events(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(locale): Extension<LocaleService>,
+    Query(filter): Query<AuditEventFilter>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = validate(&filter, state.config(), &locale) {
+        let toast = ToastTemplate::new(
+            &errors.messages().collect::<Vec<_>>().join(" "),
+        )
+        .render()?;
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            [("HX-Reswap", "none")],
+            csrf,
+            Html(toast),
+        )
+            .into_response());
+    }
+    let events =
+        AuditEventModel::filter(state.database(), &filter, Some(500)).await?;
+    let events = AuditEventsTemplate::new(&locale, &events).render()?;
+    Ok((StatusCode::OK, csrf, Html(events)).into_response())
+}
+
+#[instrument(level = "debug")]
+pub async fn export(
+    State(state): State<Arc<StateService>>,
+    Extension(locale): Extension<LocaleService>,
+    Query(query): Query<AuditExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) = validate(&query.filter, state.config(), &locale) {
+        return Ok((StatusCode::BAD_REQUEST, Json(errors)).into_response());
+    }
+    let events =
+        AuditEventModel::filter(state.database(), &query.filter, None).await?;
+    Ok(match query.format {
+        AuditExportFormat::Csv => (
+            StatusCode::OK,
+            [
+                (CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (CONTENT_DISPOSITION, "attachment; filename=\"audit.csv\""),
+            ],
+            AuditEventModel::csv(&events),
+        )
+            .into_response(),
+        AuditExportFormat::Json => (
+            StatusCode::OK,
+            [(CONTENT_DISPOSITION, "attachment; filename=\"audit.json\"")],
+            Json(events),
+        )
+            .into_response(),
+    })
+}
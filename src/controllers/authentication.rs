@@ -2,24 +2,30 @@ use std::sync::Arc;
 
 use askama::Template;
 use axum::{
-    extract::State,
+    extract::{Path, State},
     http::StatusCode,
     response::{Html, IntoResponse},
     Extension, Form, Json,
 };
 use axum_csrf::CsrfToken;
 use axum_login::AuthSession;
-use sqlx::Error;
-use tracing::{error, instrument, warn};
+use metrics::counter;
+use tracing::instrument;
 
 use crate::{
-    models::user::UserModel,
-    services::{authenticator::AuthenticatorService, state::StateService},
+    error::AppError,
+    models::{audit::AuditEventModel, user::UserModel},
+    services::{
+        accept::AcceptService, audit::AuditService,
+        authenticator::AuthenticatorService, i18n::LocaleService,
+        state::StateService,
+    },
     templates::{
         authentication::{
-            AuthenticationFormNameTemplate, AuthenticationFormPasswordTemplate,
-            AuthenticationLoginFormTemplate, AuthenticationTemplate,
+            AuthenticationLoginFormTemplate,
+            AuthenticationRegisterFormTemplate, AuthenticationTemplate,
         },
+        form::FormTemplate,
         toast::ToastTemplate,
     },
 };
@@ -28,142 +34,144 @@ use crate::{
 pub async fn authentication(
     csrf: CsrfToken,
     Extension(token): Extension<Arc<String>>,
-) -> impl IntoResponse {
-    match AuthenticationTemplate::new(&token).render() {
-        Ok(authentication) => (
-            StatusCode::OK,
-            [("HX-Retarget", "body")],
-            csrf,
-            Html(authentication),
-        )
-            .into_response(),
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
-    }
+    Extension(locale): Extension<LocaleService>,
+) -> Result<impl IntoResponse, AppError> {
+    let authentication =
+        AuthenticationTemplate::new(&token, &locale).render()?;
+    Ok((
+        StatusCode::OK,
+        [("HX-Retarget", "body")],
+        csrf,
+        Html(authentication),
+    ))
 }
 
 #[instrument(level = "debug", skip(csrf))]
 pub async fn register(
     State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    accept: AcceptService,
+    audit: AuditService,
     Form(user): Form<UserModel>,
-) -> impl IntoResponse {
-    if let Some(error) = UserModel::validate(state.database(), &user).await {
-        return (StatusCode::BAD_REQUEST, csrf, Json(error)).into_response();
-    }
-    match UserModel::create(state.database(), &user.name, &user.password).await
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(errors) =
+        UserModel::validate(state.database(), state.config(), &locale, &user)
+            .await
     {
-        Err(Error::Database(error)) => {
-            warn!("{error}");
-            return (StatusCode::CONFLICT, csrf, error.to_string())
-                .into_response();
-        }
-        Err(error) => {
-            error!("{error}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-        _ => (),
-    }
-    match ToastTemplate::new("Successful registration.").render() {
-        Ok(toast) => (StatusCode::CREATED, csrf, Html(toast)).into_response(),
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        if accept.json() {
+            return Ok(
+                (StatusCode::BAD_REQUEST, csrf, Json(errors)).into_response()
+            );
         }
+        let fields = AuthenticationRegisterFormTemplate::new(
+            &token,
+            &locale,
+            &user.name,
+            &user.password,
+        )
+        .validate(&errors)
+        .render_oob()?;
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            [("HX-Reswap", "none")],
+            csrf,
+            Html(fields),
+        )
+            .into_response());
     }
+    UserModel::create(state.database(), &user.name, &user.password).await?;
+    audit
+        .record(
+            state.database(),
+            Some(&user.name),
+            AuditEventModel::REGISTER,
+            Some(&user.name),
+        )
+        .await;
+    let toast = ToastTemplate::new(&locale.get("toast-registered")).render()?;
+    Ok((StatusCode::CREATED, csrf, Html(toast)).into_response())
 }
 
 #[instrument(level = "debug", skip(authenticator, csrf))]
 pub async fn login(
+    State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
     Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    audit: AuditService,
     mut authenticator: AuthSession<AuthenticatorService>,
     Form(user): Form<UserModel>,
-) -> impl IntoResponse {
-    let user = match authenticator.authenticate(user).await {
-        Ok(user) => user,
-        Err(error) => {
-            error!("{error}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
+) -> Result<impl IntoResponse, AppError> {
+    let name = user.name.clone();
+    let user = authenticator.authenticate(user).await?;
+    let (action, result) = if user.is_some() {
+        (AuditEventModel::LOGIN, "success")
+    } else {
+        (AuditEventModel::LOGIN_FAILED, "failure")
     };
+    counter!("logins_total", "result" => result).increment(1);
+    audit
+        .record(state.database(), Some(&name), action, Some(&name))
+        .await;
     if let Some(user) = user {
-        if let Err(error) = authenticator.login(&user).await {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        } else {
+        authenticator.login(&user).await?;
+        Ok(
             (StatusCode::SEE_OTHER, [("HX-Location", "/dashboard")], csrf)
-                .into_response()
-        }
+                .into_response(),
+        )
     } else {
-        match AuthenticationLoginFormTemplate::new(&token, true)
-            .validate(Some("Invalid credential."))
-            .render()
-        {
-            Ok(login) => (StatusCode::OK, csrf, Html(login)).into_response(),
-            Err(error) => {
-                error!("{error}");
-                StatusCode::INTERNAL_SERVER_ERROR.into_response()
-            }
-        }
+        let login = AuthenticationLoginFormTemplate::new(&token, &locale, true)
+            .validate(Some(&locale.get("auth-invalid")))
+            .render()?;
+        Ok((StatusCode::OK, csrf, Html(login)).into_response())
     }
 }
 
 #[instrument(level = "debug", skip(authenticator, csrf))]
 pub async fn logout(
+    State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
+    audit: AuditService,
     mut authenticator: AuthSession<AuthenticatorService>,
-) -> impl IntoResponse {
-    if let Err(error) = authenticator.logout().await {
-        error!("{error}");
-        StatusCode::INTERNAL_SERVER_ERROR.into_response()
-    } else {
-        (StatusCode::SEE_OTHER, [("HX-Location", "/")], csrf).into_response()
+) -> Result<impl IntoResponse, AppError> {
+    if let Some(user) = authenticator.logout().await? {
+        audit
+            .record(
+                state.database(),
+                Some(&user.name),
+                AuditEventModel::LOGOUT,
+                Some(&user.name),
+            )
+            .await;
     }
+    Ok((StatusCode::SEE_OTHER, [("HX-Location", "/")], csrf))
 }
 
 #[instrument(level = "debug", skip(csrf))]
-pub async fn validate_name(
+pub async fn validate_field(
+    Path(field): Path<String>,
     State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
     Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
     Form(user): Form<UserModel>,
-) -> impl IntoResponse {
-    match AuthenticationFormNameTemplate::new(&token, true)
-        .validate(
-            &user.name,
-            UserModel::validate_name(state.database(), &user.name).await,
-        )
-        .render()
-    {
-        Ok(form_name) => {
-            (StatusCode::OK, csrf, Html(form_name)).into_response()
-        }
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
-    }
-}
-
-#[instrument(level = "debug", skip(csrf))]
-pub async fn validate_password(
-    csrf: CsrfToken,
-    Extension(token): Extension<Arc<String>>,
-    Form(user): Form<UserModel>,
-) -> impl IntoResponse {
-    match AuthenticationFormPasswordTemplate::new(&token, true)
-        .validate(&user.password, UserModel::validate_password(&user.password))
-        .render()
-    {
-        Ok(form_password) => {
-            (StatusCode::OK, csrf, Html(form_password)).into_response()
-        }
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
-    }
+) -> Result<impl IntoResponse, AppError> {
+    let errors =
+        UserModel::validate(state.database(), state.config(), &locale, &user)
+            .await
+            .err()
+            .unwrap_or_default();
+    let field = AuthenticationRegisterFormTemplate::new(
+        &token,
+        &locale,
+        &user.name,
+        &user.password,
+    )
+    .validate(&errors)
+    .field(&field)
+    .ok_or(AppError::NotFound)?
+    .render()?;
+    Ok((StatusCode::OK, csrf, Html(field)))
 }
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    Extension, Form, Json,
+};
+use axum_csrf::CsrfToken;
+use axum_login::AuthSession;
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{
+    error::AppError,
+    models::{audit::AuditEventModel, channel::ChannelModel, user::UserModel},
+    services::{
+        accept::AcceptService, audit::AuditService,
+        authenticator::AuthenticatorService, i18n::LocaleService,
+        state::StateService,
+    },
+    templates::{
+        channel::{
+            ChannelFormTemplate, ChannelMemberFormTemplate,
+            ChannelMembersTemplate,
+        },
+        form::FormTemplate,
+        toast::ToastTemplate,
+    },
+    validation::FieldErrors,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ChannelMemberForm {
+    name: String,
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn create(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    accept: AcceptService,
+    audit: AuditService,
+    authenticator: AuthSession<AuthenticatorService>,
+    Form(channel): Form<ChannelModel>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    let channel = ChannelModel {
+        name: channel.name.trim().to_owned(),
+        ..channel
+    };
+    if let Err(errors) = ChannelModel::validate(
+        state.database(),
+        state.config(),
+        &locale,
+        &channel,
+    )
+    .await
+    {
+        let form = ChannelFormTemplate::new(&token, &locale, &channel.name);
+        return invalid(csrf, accept, form.validate(&errors), &errors);
+    }
+    let id =
+        ChannelModel::create(state.database(), &channel.name, channel.private)
+            .await?;
+    ChannelModel::add_member(state.database(), id, &user.name).await?;
+    audit
+        .record(
+            state.database(),
+            Some(&user.name),
+            AuditEventModel::CHANNEL_CREATE,
+            Some(&format!("channel/{id}")),
+        )
+        .await;
+    Ok((
+        StatusCode::CREATED,
+        [("HX-Location", format!("/dashboard?channel={id}"))],
+        csrf,
+    )
+        .into_response())
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn add_member(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    accept: AcceptService,
+    audit: AuditService,
+    authenticator: AuthSession<AuthenticatorService>,
+    Form(form): Form<ChannelMemberForm>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    ChannelModel::accessible(state.database(), id, &user.name)
+        .await?
+        .filter(|channel| channel.private)
+        .ok_or(AppError::NotFound)?;
+    let name = form.name.trim();
+    let mut members = ChannelModel::members(state.database(), id).await?;
+    let mut errors = FieldErrors::default();
+    if UserModel::find(state.database(), name).await?.is_none() {
+        errors.insert("name", locale.get("validation-unknown-user"));
+    } else if members.iter().any(|member| member == name) {
+        errors.insert("name", locale.get("validation-member"));
+    }
+    if !errors.is_empty() {
+        let form = ChannelMemberFormTemplate::new(&token, &locale, id, name);
+        return invalid(csrf, accept, form.validate(&errors), &errors);
+    }
+    ChannelModel::add_member(state.database(), id, name).await?;
+    audit
+        .record(
+            state.database(),
+            Some(&user.name),
+            AuditEventModel::CHANNEL_ADD_MEMBER,
+            Some(&format!("channel/{id}/{name}")),
+        )
+        .await;
+    members.push(name.to_owned());
+    members.sort();
+    let html = [
+        ToastTemplate::new(&locale.format(
+            "toast-channel-member-added",
+            &[("name", name.to_owned().into())],
+        ))
+        .render()?,
+        ChannelMembersTemplate::new(&locale, &members)
+            .oob()
+            .render()?,
+        ChannelMemberFormTemplate::new(&token, &locale, id, "").render_oob()?,
+    ]
+    .concat();
+    Ok((StatusCode::CREATED, csrf, Html(html)).into_response())
+}
+
+#[instrument(level = "debug", skip(csrf, form))]
+fn invalid<'a>(
+    csrf: CsrfToken,
+    accept: AcceptService,
+    form: impl FormTemplate<'a>,
+    errors: &'a FieldErrors,
+) -> Result<Response, AppError> {
+    if accept.json() {
+        return Ok(
+            (StatusCode::BAD_REQUEST, csrf, Json(errors)).into_response()
+        );
+    }
+    let fields = form.render_oob()?;
+    Ok((
+        StatusCode::BAD_REQUEST,
+        [("HX-Reswap", "none")],
+        csrf,
+        Html(fields),
+    )
+        .into_response())
+}
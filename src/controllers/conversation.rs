@@ -0,0 +1,317 @@
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{sse::Event, Html, IntoResponse, Response},
+    Extension, Form, Json,
+};
+use axum_csrf::CsrfToken;
+use axum_login::AuthSession;
+use metrics::counter;
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{
+    error::AppError,
+    models::{
+        conversation::ConversationModel, direct_message::DirectMessageModel,
+        user::UserModel,
+    },
+    services::{
+        accept::AcceptService,
+        authenticator::AuthenticatorService,
+        i18n::LocaleService,
+        state::{StateService, UserEvent},
+    },
+    templates::{
+        conversation::{
+            ConversationFormTemplate, ConversationIndexTemplate,
+            ConversationShowTemplate, ConversationUnreadTemplate,
+            DirectMessageFormTemplate,
+        },
+        form::FormTemplate,
+    },
+    validation::{validate, FieldErrors},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ConversationForm {
+    participants: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConversationIndexQuery {
+    with: Option<String>,
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn index(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+    Query(query): Query<ConversationIndexQuery>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    let conversations =
+        ConversationModel::list(state.database(), &user.name).await?;
+    let index = ConversationIndexTemplate::new(
+        &token,
+        &locale,
+        &user,
+        &conversations,
+        query.with.as_deref().unwrap_or_default(),
+    )
+    .render()?;
+    Ok(
+        (StatusCode::OK, [("HX-Retarget", "body")], csrf, Html(index))
+            .into_response(),
+    )
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn show(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    let conversation =
+        ConversationModel::find(state.database(), id, &user.name)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    let messages = DirectMessageModel::list(state.database(), id).await?;
+    ConversationModel::read(state.database(), id, &user.name).await?;
+    let show = ConversationShowTemplate::new(
+        &token,
+        &locale,
+        &user,
+        &conversation,
+        &messages,
+    )
+    .render()?;
+    Ok(
+        (StatusCode::OK, [("HX-Retarget", "body")], csrf, Html(show))
+            .into_response(),
+    )
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn create(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    accept: AcceptService,
+    authenticator: AuthSession<AuthenticatorService>,
+    Form(form): Form<ConversationForm>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    let message = DirectMessageModel {
+        id: None,
+        conversation_id: 0,
+        sender: user.name.clone(),
+        content: form.content,
+    }
+    .sanitize();
+    let mut errors = validate(&message, state.config(), &locale)
+        .err()
+        .unwrap_or_default();
+    let mut participants: Vec<String> = form
+        .participants
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty() && *name != user.name)
+        .map(str::to_owned)
+        .collect();
+    participants.sort();
+    participants.dedup();
+    if participants.is_empty()
+        || ConversationModel::PARTICIPANTS_MAX <= participants.len()
+    {
+        errors.insert(
+            "participants",
+            locale.format(
+                "validation-participants",
+                &[("max", (ConversationModel::PARTICIPANTS_MAX - 1).into())],
+            ),
+        );
+    }
+    for name in &participants {
+        if UserModel::find(state.database(), name).await?.is_none() {
+            errors.insert(
+                "participants",
+                locale.format(
+                    "validation-unknown-participant",
+                    &[("name", name.clone().into())],
+                ),
+            );
+        }
+    }
+    if !errors.is_empty() {
+        let form = ConversationFormTemplate::new(
+            &token,
+            &locale,
+            &form.participants,
+            &message.content,
+        );
+        return invalid(csrf, accept, form.validate(&errors), &errors);
+    }
+    participants.push(user.name.clone());
+    participants.sort();
+    let existing = ConversationModel::list(state.database(), &user.name)
+        .await?
+        .into_iter()
+        .find(|conversation| conversation.participants == participants);
+    let id = match existing {
+        Some(conversation) => conversation.id,
+        None => {
+            ConversationModel::create(state.database(), &participants).await?
+        }
+    };
+    deliver(&state, id, &participants, message).await?;
+    Ok((
+        StatusCode::CREATED,
+        [("HX-Location", format!("/conversation/{id}"))],
+        csrf,
+    )
+        .into_response())
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn send(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    accept: AcceptService,
+    authenticator: AuthSession<AuthenticatorService>,
+    Form(message): Form<DirectMessageModel>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    let conversation =
+        ConversationModel::find(state.database(), id, &user.name)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    let message = DirectMessageModel {
+        sender: user.name,
+        ..message
+    }
+    .sanitize();
+    if let Err(errors) = validate(&message, state.config(), &locale) {
+        let form = DirectMessageFormTemplate::new(
+            &token,
+            &locale,
+            id,
+            &message.content,
+        );
+        return invalid(csrf, accept, form.validate(&errors), &errors);
+    }
+    deliver(&state, id, &conversation.participants, message).await?;
+    let form =
+        DirectMessageFormTemplate::new(&token, &locale, id, "").render_oob()?;
+    Ok((StatusCode::CREATED, csrf, Html(form)).into_response())
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn read(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    if ConversationModel::read(state.database(), id, &user.name)
+        .await?
+        .rows_affected()
+        == 0
+    {
+        return Err(AppError::NotFound);
+    }
+    Ok((StatusCode::NO_CONTENT, [("HX-Trigger", "unread")], csrf)
+        .into_response())
+}
+
+#[instrument(level = "debug", skip(authenticator))]
+pub async fn unread(
+    State(state): State<Arc<StateService>>,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    let count = ConversationModel::unread(state.database(), &user.name).await?;
+    let unread = ConversationUnreadTemplate::new(count).render()?;
+    Ok((StatusCode::OK, Html(unread)).into_response())
+}
+
+#[instrument(level = "debug", skip(state, message))]
+async fn deliver(
+    state: &StateService,
+    id: i32,
+    participants: &[String],
+    message: DirectMessageModel,
+) -> Result<(), AppError> {
+    let message = DirectMessageModel {
+        id: Some(
+            DirectMessageModel::create(
+                state.database(),
+                id,
+                &message.sender,
+                &message.content,
+            )
+            .await?,
+        ),
+        conversation_id: id,
+        ..message
+    };
+    counter!("messages_total", "action" => "direct").increment(1);
+    for participant in participants {
+        state.notify(
+            participant,
+            (
+                Event::default()
+                    .id(state.id().to_string())
+                    .event(format!("conversation{id}")),
+                UserEvent::DirectMessage(message.clone()),
+            ),
+        );
+        if *participant != message.sender {
+            state.notify(
+                participant,
+                (
+                    Event::default().id(state.id().to_string()).event("unread"),
+                    UserEvent::Unread,
+                ),
+            );
+        }
+    }
+    Ok(())
+}
+
+#[instrument(level = "debug", skip(csrf, form))]
+fn invalid<'a>(
+    csrf: CsrfToken,
+    accept: AcceptService,
+    form: impl FormTemplate<'a>,
+    errors: &'a FieldErrors,
+) -> Result<Response, AppError> {
+    if accept.json() {
+        return Ok(
+            (StatusCode::BAD_REQUEST, csrf, Json(errors)).into_response()
+        );
+    }
+    let fields = form.render_oob()?;
+    Ok((
+        StatusCode::BAD_REQUEST,
+        [("HX-Reswap", "none")],
+        csrf,
+        Html(fields),
+    )
+        .into_response())
+}
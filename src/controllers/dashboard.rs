@@ -2,40 +2,66 @@ use std::sync::Arc;
 
 use askama::Template;
 use axum::{
+    extract::{Query, State},
     http::StatusCode,
     response::{Html, IntoResponse},
     Extension,
 };
 use axum_csrf::CsrfToken;
 use axum_login::AuthSession;
-use tracing::{error, instrument};
+use serde::Deserialize;
+use tracing::instrument;
 
 use crate::{
-    services::authenticator::AuthenticatorService,
+    error::AppError,
+    models::channel::ChannelModel,
+    services::{
+        authenticator::AuthenticatorService, i18n::LocaleService,
+        state::StateService,
+    },
     templates::dashboard::DashboardTemplate,
 };
 
+#[derive(Debug, Deserialize)]
+pub struct DashboardQuery {
+    channel: Option<i32>,
+    message: Option<i32>,
+}
+
 #[instrument(level = "debug", skip(authenticator, csrf))]
 pub async fn index(
+    State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
     Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
     authenticator: AuthSession<AuthenticatorService>,
-) -> impl IntoResponse {
+    Query(query): Query<DashboardQuery>,
+) -> Result<impl IntoResponse, AppError> {
     let Some(user) = authenticator.user else {
-        return (StatusCode::SEE_OTHER, csrf, [("HX-Location", "/")])
-            .into_response();
+        return Ok((StatusCode::SEE_OTHER, csrf, [("HX-Location", "/")])
+            .into_response());
+    };
+    let channel_id = query.channel.unwrap_or(ChannelModel::GENERAL);
+    let channel =
+        ChannelModel::accessible(state.database(), channel_id, &user.name)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    let channels = ChannelModel::visible(state.database(), &user.name).await?;
+    let members = if channel.private {
+        ChannelModel::members(state.database(), channel_id).await?
+    } else {
+        Vec::new()
     };
-    match DashboardTemplate::new(&token, &user.name).render() {
-        Ok(dashboard) => (
-            StatusCode::OK,
-            [("HX-Retarget", "body")],
-            csrf,
-            Html(dashboard),
-        )
-            .into_response(),
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
-    }
+    let dashboard = DashboardTemplate::new(
+        &token, &locale, &user, &channel, &channels, &members,
+    )
+    .message(query.message)
+    .render()?;
+    Ok((
+        StatusCode::OK,
+        [("HX-Retarget", "body")],
+        csrf,
+        Html(dashboard),
+    )
+        .into_response())
 }
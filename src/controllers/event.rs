@@ -0,0 +1,58 @@
+use std::{error::Error, sync::Arc};
+
+use askama::Template;
+use axum::{
+    extract::State,
+    response::{
+        sse::{Event, KeepAlive},
+        Sse,
+    },
+};
+use axum_login::AuthSession;
+use metrics::counter;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tracing::{error, instrument};
+
+use crate::{
+    error::AppError,
+    services::{
+        authenticator::AuthenticatorService,
+        state::{StateService, UserEvent},
+    },
+    templates::conversation::DirectMessageTemplate,
+};
+
+#[instrument(level = "debug", skip(authenticator))]
+pub async fn events(
+    State(state): State<Arc<StateService>>,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<
+    Sse<impl Stream<Item = Result<Event, Box<dyn Error + Send + Sync>>>>,
+    AppError,
+> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    let events = state.subscribe(&user.name);
+    Ok(Sse::new(BroadcastStream::new(events).map(move |event| {
+        match event {
+            Ok((event, user_event)) => match &user_event {
+                UserEvent::DirectMessage(message) => {
+                    DirectMessageTemplate::new(&user.name, message).render()
+                }
+                UserEvent::Unread | UserEvent::Notification => {
+                    Ok(String::new())
+                }
+            }
+            .map(|data| event.data(data))
+            .map_err(|error| {
+                error!("{error}");
+                Box::new(error) as Box<dyn Error + Send + Sync>
+            }),
+            Err(error) => {
+                error!("{error}");
+                counter!("broadcast_lag_events_total").increment(1);
+                Err(Box::new(error) as Box<dyn Error + Send + Sync>)
+            }
+        }
+    }))
+    .keep_alive(KeepAlive::new()))
+}
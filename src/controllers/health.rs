@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde::Serialize;
+use tracing::{instrument, warn};
+
+use crate::services::{migrator::MigratorService, state::StateService};
+
+#[derive(Debug, Serialize)]
+pub struct HealthStatus {
+    status: &'static str,
+    version: &'static str,
+    uptime: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checks: Option<HealthChecks>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthChecks {
+    database: bool,
+    migrations: bool,
+}
+
+impl HealthStatus {
+    fn new(
+        state: &StateService,
+        status: &'static str,
+        checks: Option<HealthChecks>,
+    ) -> Self {
+        Self {
+            status,
+            version: env!("CARGO_PKG_VERSION"),
+            uptime: state.uptime().as_secs(),
+            checks,
+        }
+    }
+}
+
+#[instrument(level = "trace")]
+pub async fn healthz(
+    State(state): State<Arc<StateService>>,
+) -> impl IntoResponse {
+    (StatusCode::OK, Json(HealthStatus::new(&state, "ok", None)))
+}
+
+#[instrument(level = "trace")]
+pub async fn readyz(
+    State(state): State<Arc<StateService>>,
+) -> impl IntoResponse {
+    let database = match state.database().acquire().await {
+        Ok(..) => true,
+        Err(error) => {
+            warn!("{error}");
+            false
+        }
+    };
+    let migrations = database
+        && match MigratorService::pending(state.database()).await {
+            Ok(pending) => pending.is_empty(),
+            Err(error) => {
+                warn!("{error}");
+                false
+            }
+        };
+    let checks = HealthChecks {
+        database,
+        migrations,
+    };
+    if database && migrations {
+        (
+            StatusCode::OK,
+            Json(HealthStatus::new(&state, "ok", Some(checks))),
+        )
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(HealthStatus::new(&state, "unavailable", Some(checks))),
+        )
+    }
+}
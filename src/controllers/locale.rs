@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Form,
+};
+use axum_csrf::CsrfToken;
+use axum_login::AuthSession;
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{
+    error::AppError,
+    models::user::UserModel,
+    services::{
+        authenticator::AuthenticatorService, i18n::I18nService,
+        state::StateService,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct LocaleForm {
+    locale: String,
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn update(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    authenticator: AuthSession<AuthenticatorService>,
+    Form(form): Form<LocaleForm>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    let locale = match form.locale.as_str() {
+        "" => None,
+        locale if I18nService::LOCALES.contains(&locale) => Some(locale),
+        _ => return Err(AppError::NotFound),
+    };
+    UserModel::set_locale(state.database(), &user.name, locale).await?;
+    Ok((StatusCode::OK, [("HX-Refresh", "true")], csrf).into_response())
+}
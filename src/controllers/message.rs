@@ -2,301 +2,574 @@ use std::{error::Error, sync::Arc};
 
 use askama::Template;
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
     response::{
         sse::{Event, KeepAlive},
-        Html, IntoResponse, Redirect, Sse,
+        Html, IntoResponse, Redirect, Response, Sse,
     },
     Extension, Form, Json,
 };
 use axum_csrf::CsrfToken;
+use axum_login::AuthSession;
+use metrics::counter;
+use serde::Deserialize;
 use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
-use tracing::{error, instrument, warn};
+use tracing::{error, instrument};
 
 use crate::{
-    models::message::MessageModel,
-    services::state::StateService,
+    error::AppError,
+    models::{
+        audit::AuditEventModel,
+        channel::{ChannelModel, ChannelQuery},
+        message::MessageModel,
+        notification::NotificationModel,
+        pin::PinModel,
+        reaction::ReactionModel,
+        star::StarModel,
+        tag::TagModel,
+    },
+    services::{
+        accept::AcceptService,
+        audit::AuditService,
+        authenticator::AuthenticatorService,
+        i18n::LocaleService,
+        message::MessageService,
+        notification::NotificationService,
+        state::{MessageEvent, StateService},
+    },
     templates::{
+        form::FormTemplate,
         message::{
-            MessageEventTemplate, MessageFormContentTemplate,
-            MessageFormTitleTemplate, MessageIndexTemplate,
-            MessageShowTemplate,
+            MessageEventTemplate, MessageFormTemplate, MessageIndexTemplate,
+            MessagePinTemplate, MessagePinsTemplate, MessageReactionsTemplate,
+            MessageReplyTemplate, MessageShowTemplate, MessageStarTemplate,
         },
         toast::ToastTemplate,
     },
+    validation::{validate, FieldErrors},
 };
 
-#[instrument(level = "debug", skip(csrf))]
+#[derive(Debug, Deserialize)]
+pub struct ReactionForm {
+    reaction: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MessageIndexQuery {
+    channel: Option<i32>,
+    #[serde(default)]
+    starred: bool,
+    #[serde(default)]
+    tags: String,
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
 pub async fn show(
     Path(id): Path<i32>,
     State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
     Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
     headers: HeaderMap,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, AppError> {
     if headers.get("HX-Request").is_none() {
-        return Redirect::to("/dashboard").into_response();
+        return Ok(Redirect::to("/dashboard").into_response());
     }
-    let message = match MessageModel::find(state.database(), id).await {
-        Ok(Some(message)) => message,
-        Ok(None) => return (StatusCode::NOT_FOUND, csrf).into_response(),
-        Err(error) => {
-            error!("{error}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-    };
-    let Some(id) = message.id else {
-        error!("missing id");
-        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-    };
-    match MessageShowTemplate::new(&token, id, &message.title, &message.content)
-        .render()
-    {
-        Ok(show) => (StatusCode::OK, csrf, Html(show)).into_response(),
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    MessageService::message(&state, &user.name, id).await?;
+    let thread = MessageModel::thread(state.database(), id).await?;
+    let message = thread
+        .iter()
+        .find(|message| message.id == Some(id))
+        .ok_or(AppError::NotFound)?;
+    let starred = StarModel::exists(state.database(), id, &user.name).await?;
+    let tags = message.tags.join(", ");
+    let mut show =
+        MessageShowTemplate::new(&token, &locale, id, message, &thread, &tags)
+            .star(starred);
+    if user.is_moderator() {
+        show = show.pin(PinModel::exists(state.database(), id).await?);
     }
+    let show = show.render()?;
+    Ok((StatusCode::OK, csrf, Html(show)).into_response())
 }
 
-#[instrument(level = "debug", skip(csrf))]
+#[instrument(level = "debug", skip(authenticator, csrf))]
 pub async fn index(
     State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
     Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+    Query(query): Query<MessageIndexQuery>,
     headers: HeaderMap,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, AppError> {
     if headers.get("HX-Request").is_none() {
-        return Redirect::to("/dashboard").into_response();
+        return Ok(Redirect::to("/dashboard").into_response());
     }
-    let messages = match MessageModel::all(state.database()).await {
-        Ok(messages) => messages,
-        Err(error) => {
-            error!("{error}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let channel_id = MessageService::channel(
+        &state,
+        &user.name,
+        query.channel.unwrap_or(ChannelModel::GENERAL),
+    )
+    .await?;
+    let filter = TagModel::parse(&query.tags);
+    let (messages, pinned) = if query.starred {
+        let starred = MessageModel::starred(
+            state.database(),
+            channel_id,
+            &user.name,
+            &filter,
+        )
+        .await?;
+        (starred, Vec::new())
+    } else {
+        (
+            MessageModel::roots(state.database(), channel_id, &filter).await?,
+            MessageModel::pinned(state.database(), channel_id).await?,
+        )
     };
-    match MessageIndexTemplate::new(&token, &messages).render() {
-        Ok(index) => (StatusCode::OK, csrf, Html(index)).into_response(),
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
-    }
+    let tags = TagModel::counts(state.database(), channel_id).await?;
+    let index = MessageIndexTemplate::new(
+        &token,
+        &locale,
+        channel_id,
+        &messages,
+        &pinned,
+        query.starred,
+    )
+    .tags(&tags, &filter)
+    .render()?;
+    Ok((StatusCode::OK, csrf, Html(index)).into_response())
 }
 
-#[instrument(level = "debug", skip(csrf))]
+#[instrument(level = "debug", skip(authenticator, csrf))]
 pub async fn create(
     State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    accept: AcceptService,
+    messages: MessageService,
+    authenticator: AuthSession<AuthenticatorService>,
     Form(message): Form<MessageModel>,
-) -> impl IntoResponse {
-    if let Some(error) = MessageModel::validate(&message) {
-        return (StatusCode::BAD_REQUEST, csrf, Json(error)).into_response();
+) -> Result<impl IntoResponse, AppError> {
+    let message = message.sanitize();
+    if let Err(errors) = validate(&message, state.config(), &locale) {
+        let tags = message.tags.join(", ");
+        let form = MessageFormTemplate::new(
+            &token,
+            &locale,
+            0,
+            &message.title,
+            &message.content,
+            &tags,
+        );
+        return invalid(csrf, accept, form, &errors);
     }
-    let id = match MessageModel::create(
-        state.database(),
-        &message.title,
-        &message.content,
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let id = messages
+        .create(
+            &user.name,
+            MessageModel {
+                parent_id: None,
+                ..message
+            },
+        )
+        .await?
+        .id
+        .ok_or(AppError::NotFound)?;
+    let toast = ToastTemplate::new(
+        &locale.format("toast-message-sent", &[("id", id.into())]),
     )
-    .await
-    {
-        Ok(query) => query.last_insert_id(),
-        Err(sqlx::Error::Database(error)) => {
-            warn!("{error}");
-            return (StatusCode::CONFLICT, csrf, error.to_string())
-                .into_response();
-        }
-        Err(error) => {
-            error!("{error}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-    };
-    let id = match i32::try_from(id) {
-        Ok(id) => id,
-        Err(error) => {
-            error!("{error}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-    };
-    if let Err(error) = state.messages().send((
-        Event::default().id(state.id().to_string()).event("create"),
-        Some(MessageModel {
-            id: Some(id),
-            title: message.title,
-            content: message.content,
-        }),
-    )) {
-        error!("{error}");
-        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-    }
-    match ToastTemplate::new(&format!("Message #{id} sent.")).render() {
-        Ok(toast) => (StatusCode::CREATED, csrf, Html(toast)).into_response(),
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
-    }
+    .render()?;
+    Ok((StatusCode::CREATED, csrf, Html(toast)).into_response())
 }
 
-#[instrument(level = "debug", skip(csrf))]
+#[instrument(level = "debug", skip(authenticator, csrf))]
 pub async fn update(
     Path(id): Path<i32>,
     State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    accept: AcceptService,
+    messages: MessageService,
+    authenticator: AuthSession<AuthenticatorService>,
     Form(message): Form<MessageModel>,
-) -> impl IntoResponse {
-    if let Some(error) = MessageModel::validate(&message) {
-        return (StatusCode::BAD_REQUEST, csrf, Json(error)).into_response();
+) -> Result<impl IntoResponse, AppError> {
+    let message = message.sanitize();
+    if let Err(errors) = validate(&message, state.config(), &locale) {
+        let tags = message.tags.join(", ");
+        let form = MessageFormTemplate::new(
+            &token,
+            &locale,
+            id,
+            &message.title,
+            &message.content,
+            &tags,
+        );
+        return invalid(csrf, accept, form, &errors);
     }
-    match MessageModel::update(
-        state.database(),
-        id,
-        &message.title,
-        &message.content,
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    messages.update(&user.name, id, message).await?;
+    let toast = ToastTemplate::new(
+        &locale.format("toast-message-sent", &[("id", id.into())]),
     )
-    .await
-    {
-        Err(sqlx::Error::Database(error)) => {
-            warn!("{error}");
-            return (StatusCode::CONFLICT, csrf, error.to_string())
-                .into_response();
-        }
-        Err(error) => {
-            error!("{error}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-        _ => (),
-    }
-    if let Err(error) = state.messages().send((
-        Event::default()
-            .id(state.id().to_string())
-            .event(format!("update{id}")),
-        Some(MessageModel {
-            id: Some(id),
-            title: message.title,
-            content: message.content,
-        }),
-    )) {
-        error!("{error}");
-        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-    }
-    match ToastTemplate::new(&format!("Message #{id} sent.")).render() {
-        Ok(toast) => (StatusCode::OK, csrf, Html(toast)).into_response(),
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
-    }
+    .render()?;
+    Ok((StatusCode::OK, csrf, Html(toast)).into_response())
 }
 
-#[instrument(level = "debug", skip(csrf))]
+#[instrument(level = "debug", skip(authenticator, csrf))]
 pub async fn destroy(
     Path(id): Path<i32>,
-    State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
-) -> impl IntoResponse {
-    match MessageModel::delete(state.database(), id).await {
-        Err(sqlx::Error::Database(error)) => {
-            warn!("{error}");
-            return (StatusCode::CONFLICT, csrf, error.to_string())
-                .into_response();
-        }
-        Err(error) => {
-            error!("{error}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-        _ => (),
-    };
-    if let Err(error) = state.messages().send((
-        Event::default()
-            .id(state.id().to_string())
-            .event(format!("destroy{id}")),
-        None,
-    )) {
-        error!("{error}");
-        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-    }
-    match ToastTemplate::new(&format!("Message #{id} deleted.")).render() {
-        Ok(toast) => (StatusCode::OK, csrf, Html(toast)).into_response(),
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
-    }
+    Extension(locale): Extension<LocaleService>,
+    messages: MessageService,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    messages.delete(&user.name, id).await?;
+    let toast = ToastTemplate::new(
+        &locale.format("toast-message-deleted", &[("id", id.into())]),
+    )
+    .render()?;
+    Ok((StatusCode::OK, csrf, Html(toast)))
 }
 
-#[instrument(level = "debug")]
+#[instrument(level = "debug", skip(authenticator))]
 pub async fn events(
     State(state): State<Arc<StateService>>,
     Extension(token): Extension<Arc<String>>,
-) -> Sse<impl Stream<Item = Result<Event, Box<dyn Error + Send + Sync>>>> {
-    Sse::new(BroadcastStream::new(state.messages().subscribe()).map(
-        move |event| match event {
-            Ok((event, message)) => {
-                Ok(event.data(if let Some(message) = message {
-                    match MessageEventTemplate::new(&token, &message).render() {
-                        Ok(event) => event,
-                        Err(error) => {
-                            error!("{error}");
-                            return Err(
-                                Box::new(error) as Box<dyn Error + Send + Sync>
-                            );
-                        }
-                    }
-                } else {
-                    String::new()
-                }))
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+    Query(query): Query<ChannelQuery>,
+) -> Result<
+    Sse<impl Stream<Item = Result<Event, Box<dyn Error + Send + Sync>>>>,
+    AppError,
+> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let channel_id =
+        MessageService::channel(&state, &user.name, query.id()).await?;
+    let messages = state.messages(channel_id).subscribe();
+    Ok(Sse::new(BroadcastStream::new(messages).map(move |event| {
+        match event {
+            Ok((event, message)) => match &message {
+                MessageEvent::Message(message)
+                    if message.parent_id.is_some() =>
+                {
+                    MessageReplyTemplate::new(&locale, message, &[]).render()
+                }
+                MessageEvent::Message(message) => {
+                    MessageEventTemplate::new(&token, &locale, message).render()
+                }
+                MessageEvent::Reactions(message) => {
+                    MessageReactionsTemplate::new(&token, &locale, message)
+                        .render()
+                }
+                MessageEvent::Pins(pinned) => {
+                    MessagePinsTemplate::new(&locale, pinned).render()
+                }
+                MessageEvent::Deleted => Ok(String::new()),
             }
+            .map(|data| event.data(data))
+            .map_err(|error| {
+                error!("{error}");
+                Box::new(error) as Box<dyn Error + Send + Sync>
+            }),
             Err(error) => {
                 error!("{error}");
+                counter!("broadcast_lag_events_total").increment(1);
                 Err(Box::new(error) as Box<dyn Error + Send + Sync>)
             }
-        },
-    ))
-    .keep_alive(KeepAlive::new())
+        }
+    }))
+    .keep_alive(KeepAlive::new()))
 }
 
 #[instrument(level = "debug", skip(csrf))]
-pub async fn validate_title(
+pub async fn validate_field(
+    Path((id, field)): Path<(i32, String)>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    Form(message): Form<MessageModel>,
+) -> Result<impl IntoResponse, AppError> {
+    let message = message.sanitize();
+    let errors = validate(&message, state.config(), &locale)
+        .err()
+        .unwrap_or_default();
+    let tags = message.tags.join(", ");
+    let field = MessageFormTemplate::new(
+        &token,
+        &locale,
+        id,
+        &message.title,
+        &message.content,
+        &tags,
+    )
+    .validate(&errors)
+    .field(&field)
+    .ok_or(AppError::NotFound)?
+    .render()?;
+    Ok((StatusCode::OK, csrf, Html(field)))
+}
+
+#[instrument(level = "debug", skip(csrf))]
+pub async fn preview(
     Path(id): Path<i32>,
     csrf: CsrfToken,
     Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
     Form(message): Form<MessageModel>,
-) -> impl IntoResponse {
-    match MessageFormTitleTemplate::new(&token, id, &message.title)
-        .validate(MessageModel::validate_title(&message.title))
-        .render()
-    {
-        Ok(form_title) => {
-            (StatusCode::OK, csrf, Html(form_title)).into_response()
-        }
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
+) -> Result<impl IntoResponse, AppError> {
+    let message = message.sanitize();
+    let preview = MessageFormTemplate::new(
+        &token,
+        &locale,
+        id,
+        &message.title,
+        &message.content,
+        "",
+    )
+    .preview()
+    .render()?;
+    Ok((StatusCode::OK, csrf, Html(preview)))
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn reply(
+    Path(parent): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    accept: AcceptService,
+    messages: MessageService,
+    authenticator: AuthSession<AuthenticatorService>,
+    Form(message): Form<MessageModel>,
+) -> Result<impl IntoResponse, AppError> {
+    let message = message.sanitize();
+    if let Err(errors) = validate(&message, state.config(), &locale) {
+        let form = MessageFormTemplate::reply(
+            &token,
+            &locale,
+            parent,
+            &message.title,
+            &message.content,
+        );
+        return invalid(csrf, accept, form, &errors);
     }
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let id = messages
+        .create(
+            &user.name,
+            MessageModel {
+                parent_id: Some(parent),
+                tags: Vec::new(),
+                ..message
+            },
+        )
+        .await?
+        .id
+        .ok_or(AppError::NotFound)?;
+    let toast = ToastTemplate::new(
+        &locale.format("toast-reply-sent", &[("id", id.into())]),
+    )
+    .render()?;
+    Ok((StatusCode::CREATED, csrf, Html(toast)).into_response())
 }
 
 #[instrument(level = "debug", skip(csrf))]
-pub async fn validate_content(
-    Path(id): Path<i32>,
+pub async fn reply_validate_field(
+    Path((parent, field)): Path<(i32, String)>,
+    State(state): State<Arc<StateService>>,
     csrf: CsrfToken,
     Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
     Form(message): Form<MessageModel>,
-) -> impl IntoResponse {
-    match MessageFormContentTemplate::new(&token, id, &message.content)
-        .validate(MessageModel::validate_content(&message.content))
-        .render()
-    {
-        Ok(form_content) => {
-            (StatusCode::OK, csrf, Html(form_content)).into_response()
-        }
-        Err(error) => {
-            error!("{error}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+) -> Result<impl IntoResponse, AppError> {
+    let message = message.sanitize();
+    let errors = validate(&message, state.config(), &locale)
+        .err()
+        .unwrap_or_default();
+    let field = MessageFormTemplate::reply(
+        &token,
+        &locale,
+        parent,
+        &message.title,
+        &message.content,
+    )
+    .validate(&errors)
+    .field(&field)
+    .ok_or(AppError::NotFound)?
+    .render()?;
+    Ok((StatusCode::OK, csrf, Html(field)))
+}
+
+#[instrument(level = "debug", skip(csrf))]
+pub async fn reply_preview(
+    Path(parent): Path<i32>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    Form(message): Form<MessageModel>,
+) -> Result<impl IntoResponse, AppError> {
+    let message = message.sanitize();
+    let preview = MessageFormTemplate::reply(
+        &token,
+        &locale,
+        parent,
+        &message.title,
+        &message.content,
+    )
+    .preview()
+    .render()?;
+    Ok((StatusCode::OK, csrf, Html(preview)))
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn react(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(locale): Extension<LocaleService>,
+    accept: AcceptService,
+    authenticator: AuthSession<AuthenticatorService>,
+    Form(form): Form<ReactionForm>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    MessageService::message(&state, &user.name, id).await?;
+    if !ReactionModel::valid(&form.reaction) {
+        let mut errors = FieldErrors::default();
+        errors.insert(
+            "reaction",
+            locale.format(
+                "validation-invalid",
+                &[("field", locale.get("field-reaction").into())],
+            ),
+        );
+        if accept.json() {
+            return Ok(
+                (StatusCode::BAD_REQUEST, csrf, Json(errors)).into_response()
+            );
         }
+        let toast =
+            ToastTemplate::new(errors.get("reaction").unwrap_or_default())
+                .render()?;
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            [("HX-Reswap", "none")],
+            csrf,
+            Html(toast),
+        )
+            .into_response());
     }
+    let current = ReactionModel::find(state.database(), id, &user.name).await?;
+    if current.as_deref() == Some(form.reaction.as_str()) {
+        ReactionModel::delete(state.database(), id, &user.name).await?;
+    } else {
+        ReactionModel::set(state.database(), id, &user.name, &form.reaction)
+            .await?;
+    }
+    counter!("reactions_total").increment(1);
+    if let Some(message) = MessageModel::find(state.database(), id).await? {
+        state.messages(message.channel_id).send((
+            Event::default()
+                .id(state.id().to_string())
+                .event(format!("reactions{id}")),
+            MessageEvent::Reactions(message),
+        ))?;
+    }
+    Ok((StatusCode::NO_CONTENT, csrf).into_response())
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn star(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    MessageService::message(&state, &user.name, id).await?;
+    let starred = StarModel::exists(state.database(), id, &user.name).await?;
+    if starred {
+        StarModel::delete(state.database(), id, &user.name).await?;
+    } else {
+        StarModel::create(state.database(), id, &user.name).await?;
+    }
+    let star =
+        MessageStarTemplate::new(&token, &locale, id, !starred).render()?;
+    Ok((StatusCode::OK, csrf, Html(star)))
+}
+
+#[instrument(level = "debug", skip(authenticator, audit, csrf))]
+pub async fn pin(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    audit: AuditService,
+    notifications: NotificationService,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let message = MessageService::message(&state, &user.name, id).await?;
+    let pinned = PinModel::exists(state.database(), id).await?;
+    let action = if pinned {
+        PinModel::delete(state.database(), id).await?;
+        AuditEventModel::MESSAGE_UNPIN
+    } else {
+        PinModel::create(state.database(), id, &user.name).await?;
+        AuditEventModel::MESSAGE_PIN
+    };
+    audit
+        .record(
+            state.database(),
+            Some(&user.name),
+            action,
+            Some(&format!("message/{id}")),
+        )
+        .await;
+    notifications
+        .author(&message, NotificationModel::ADMIN, &user.name, Some(action))
+        .await?;
+    let pins =
+        MessageModel::pinned(state.database(), message.channel_id).await?;
+    state.messages(message.channel_id).send((
+        Event::default().id(state.id().to_string()).event("pins"),
+        MessageEvent::Pins(pins),
+    ))?;
+    let pin = MessagePinTemplate::new(&token, &locale, id, !pinned).render()?;
+    Ok((StatusCode::OK, csrf, Html(pin)))
+}
+
+#[instrument(level = "debug", skip(csrf, form))]
+fn invalid<'a>(
+    csrf: CsrfToken,
+    accept: AcceptService,
+    form: MessageFormTemplate<'a>,
+    errors: &'a FieldErrors,
+) -> Result<Response, AppError> {
+    if accept.json() {
+        return Ok(
+            (StatusCode::BAD_REQUEST, csrf, Json(errors)).into_response()
+        );
+    }
+    let fields = form.validate(errors).render_oob()?;
+    Ok((
+        StatusCode::BAD_REQUEST,
+        [("HX-Reswap", "none")],
+        csrf,
+        Html(fields),
+    )
+        .into_response())
 }
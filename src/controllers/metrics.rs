@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE, WWW_AUTHENTICATE},
+        HeaderMap, StatusCode,
+    },
+    response::IntoResponse,
+};
+use metrics::gauge;
+use tracing::{instrument, warn};
+
+use crate::services::state::StateService;
+
+#[instrument(level = "trace", skip(headers))]
+pub async fn metrics(
+    State(state): State<Arc<StateService>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Some(token) = &state.config().metrics_token {
+        let authorization = headers
+            .get(AUTHORIZATION)
+            .and_then(|authorization| authorization.to_str().ok())
+            .and_then(|authorization| authorization.strip_prefix("Bearer "));
+        if authorization != Some(token.as_str()) {
+            warn!("invalid metrics token");
+            return (StatusCode::UNAUTHORIZED, [(WWW_AUTHENTICATE, "Bearer")])
+                .into_response();
+        }
+    }
+    let database = state.database();
+    let idle = database.num_idle();
+    gauge!("database_connections", "state" => "idle").set(idle as f64);
+    gauge!("database_connections", "state" => "active")
+        .set(database.size().saturating_sub(idle as u32) as f64);
+    gauge!("database_connections_max")
+        .set(database.options().get_max_connections() as f64);
+    gauge!("sse_subscribers").set(state.subscribers() as f64);
+    (
+        StatusCode::OK,
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics().render(),
+    )
+        .into_response()
+}
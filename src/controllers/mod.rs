@@ -1,3 +1,13 @@
+pub mod api;
+pub mod audit;
 pub mod authentication;
+pub mod channel;
+pub mod conversation;
 pub mod dashboard;
+pub mod event;
+pub mod health;
+pub mod locale;
 pub mod message;
+pub mod metrics;
+pub mod notification;
+pub mod tag;
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    Extension, Form,
+};
+use axum_csrf::CsrfToken;
+use axum_login::AuthSession;
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{
+    error::AppError,
+    models::{message::MessageModel, notification::NotificationModel},
+    services::{
+        authenticator::AuthenticatorService, i18n::LocaleService,
+        state::StateService,
+    },
+    templates::{
+        notification::{
+            NotificationIndexTemplate, NotificationListTemplate,
+            NotificationMenuTemplate, NotificationUnreadTemplate,
+        },
+        toast::ToastTemplate,
+    },
+};
+
+const RECENT: i64 = 10;
+const PER_PAGE: i64 = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationPageQuery {
+    page: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationPreferenceForm {
+    kind: String,
+    #[serde(default)]
+    enabled: bool,
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn index(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+    Query(query): Query<NotificationPageQuery>,
+) -> Result<Response, AppError> {
+    let user = authenticator.user.ok_or(AppError::Unauthorized)?;
+    let (notifications, page, pages) =
+        page(&state, &user.name, query.page).await?;
+    let preferences =
+        NotificationModel::preferences(state.database(), &user.name).await?;
+    let list = NotificationListTemplate::new(
+        &token,
+        &locale,
+        &notifications,
+        page,
+        pages,
+    );
+    let index = NotificationIndexTemplate::new(
+        &token,
+        &locale,
+        &user,
+        list,
+        &preferences,
+    )
+    .render()?;
+    Ok(
+        (StatusCode::OK, [("HX-Retarget", "body")], csrf, Html(index))
+            .into_response(),
+    )
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn list(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+    Query(query): Query<NotificationPageQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let (notifications, page, pages) =
+        page(&state, &user.name, query.page).await?;
+    let list = NotificationListTemplate::new(
+        &token,
+        &locale,
+        &notifications,
+        page,
+        pages,
+    )
+    .render()?;
+    Ok((StatusCode::OK, csrf, Html(list)))
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn menu(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(token): Extension<Arc<String>>,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let notifications =
+        NotificationModel::page(state.database(), &user.name, RECENT, 0)
+            .await?;
+    let menu = NotificationMenuTemplate::new(&token, &locale, &notifications)
+        .render()?;
+    Ok((StatusCode::OK, csrf, Html(menu)))
+}
+
+#[instrument(level = "debug", skip(authenticator))]
+pub async fn unread(
+    State(state): State<Arc<StateService>>,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let count = NotificationModel::unread(state.database(), &user.name).await?;
+    let unread = NotificationUnreadTemplate::new(count).render()?;
+    Ok((StatusCode::OK, Html(unread)))
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn open(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let notification =
+        NotificationModel::find(state.database(), id, &user.name)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    NotificationModel::read(state.database(), id, &user.name).await?;
+    let location = match notification.message_id {
+        Some(message_id) => MessageModel::find(state.database(), message_id)
+            .await?
+            .map(|message| {
+                format!(
+                    "/dashboard?channel={}&message={message_id}",
+                    message.channel_id
+                )
+            }),
+        None => None,
+    };
+    let Some(location) = location else {
+        return Ok((
+            StatusCode::NO_CONTENT,
+            [("HX-Trigger", "notifications")],
+            csrf,
+        )
+            .into_response());
+    };
+    Ok((
+        StatusCode::OK,
+        [("HX-Trigger", "notifications")],
+        [("HX-Location", location)],
+        csrf,
+    )
+        .into_response())
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn read(
+    Path(id): Path<i32>,
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let query =
+        NotificationModel::read(state.database(), id, &user.name).await?;
+    if query.rows_affected() == 0 {
+        NotificationModel::find(state.database(), id, &user.name)
+            .await?
+            .ok_or(AppError::NotFound)?;
+    }
+    Ok((
+        StatusCode::NO_CONTENT,
+        [("HX-Trigger", "notifications")],
+        csrf,
+    ))
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn read_all(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    authenticator: AuthSession<AuthenticatorService>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    NotificationModel::read_all(state.database(), &user.name).await?;
+    Ok((
+        StatusCode::NO_CONTENT,
+        [("HX-Trigger", "notifications")],
+        csrf,
+    ))
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn preferences(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    Extension(locale): Extension<LocaleService>,
+    authenticator: AuthSession<AuthenticatorService>,
+    Form(form): Form<NotificationPreferenceForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    if !NotificationModel::KINDS.contains(&form.kind.as_str()) {
+        return Err(AppError::NotFound);
+    }
+    NotificationModel::prefer(
+        state.database(),
+        &user.name,
+        &form.kind,
+        form.enabled,
+    )
+    .await?;
+    let toast =
+        ToastTemplate::new(&locale.get("toast-notification-preferences"))
+            .render()?;
+    Ok((StatusCode::OK, csrf, Html(toast)))
+}
+
+#[instrument(level = "debug", skip(state))]
+async fn page(
+    state: &StateService,
+    user: &str,
+    page: Option<i64>,
+) -> Result<(Vec<NotificationModel>, i64, i64), AppError> {
+    let count = NotificationModel::count(state.database(), user).await?;
+    let pages = ((count + PER_PAGE - 1) / PER_PAGE).max(1);
+    let page = page.unwrap_or(1).clamp(1, pages);
+    let notifications = NotificationModel::page(
+        state.database(),
+        user,
+        PER_PAGE,
+        (page - 1) * PER_PAGE,
+    )
+    .await?;
+    Ok((notifications, page, pages))
+}
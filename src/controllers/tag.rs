@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{Html, IntoResponse},
+};
+use axum_csrf::CsrfToken;
+use axum_login::AuthSession;
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{
+    error::AppError,
+    models::tag::TagModel,
+    services::{authenticator::AuthenticatorService, state::StateService},
+    templates::tag::TagSuggestionsTemplate,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct TagSuggestionsQuery {
+    #[serde(default)]
+    tags: String,
+}
+
+#[instrument(level = "debug", skip(authenticator, csrf))]
+pub async fn suggestions(
+    State(state): State<Arc<StateService>>,
+    csrf: CsrfToken,
+    authenticator: AuthSession<AuthenticatorService>,
+    Query(query): Query<TagSuggestionsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = authenticator.user.as_ref().ok_or(AppError::Unauthorized)?;
+    let names = TagModel::visible(state.database(), &user.name).await?;
+    let suggestions = TagModel::suggest(&query.tags, &names);
+    let suggestions = TagSuggestionsTemplate::new(&suggestions).render()?;
+    Ok((StatusCode::OK, csrf, Html(suggestions)))
+}
@@ -0,0 +1,82 @@
+use std::borrow::Cow;
+
+use sqlx::Pool;
+
+#[cfg(not(any(feature = "mysql", feature = "postgres", feature = "sqlite")))]
+compile_error!("enable one of the `mysql`, `postgres` or `sqlite` features");
+
+#[cfg(any(
+    all(feature = "mysql", feature = "postgres"),
+    all(feature = "mysql", feature = "sqlite"),
+    all(feature = "postgres", feature = "sqlite"),
+))]
+compile_error!(
+    "enable only one of the `mysql`, `postgres` or `sqlite` features"
+);
+
+#[cfg(feature = "mysql")]
+pub type Database = sqlx::MySql;
+
+#[cfg(feature = "postgres")]
+pub type Database = sqlx::Postgres;
+
+#[cfg(feature = "sqlite")]
+pub type Database = sqlx::Sqlite;
+
+pub type DatabasePool = Pool<Database>;
+
+pub type DatabaseQueryResult = <Database as sqlx::Database>::QueryResult;
+
+#[cfg(not(feature = "postgres"))]
+pub fn sql(query: &'static str) -> Cow<'static, str> {
+    Cow::Borrowed(query)
+}
+
+#[cfg(feature = "postgres")]
+pub fn sql(query: &'static str) -> Cow<'static, str> {
+    Cow::Owned(number(query))
+}
+
+#[cfg(any(test, feature = "postgres"))]
+fn number(query: &str) -> String {
+    let mut sql = String::with_capacity(query.len() + 16);
+    let mut quote = None;
+    let mut index = 0;
+    for c in query.chars() {
+        match (quote, c) {
+            (None, '?') => {
+                index += 1;
+                sql.push('$');
+                sql.push_str(&index.to_string());
+                continue;
+            }
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(open), _) if open == c => quote = None,
+            _ => {}
+        }
+        sql.push(c);
+    }
+    sql
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_placeholders() {
+        assert_eq!(
+            number("SELECT * FROM t WHERE a = ? AND b IN (?, ?) LIMIT ?;"),
+            "SELECT * FROM t WHERE a = $1 AND b IN ($2, $3) LIMIT $4;"
+        );
+        assert_eq!(number("SELECT 1;"), "SELECT 1;");
+    }
+
+    #[test]
+    fn keeps_quoted_question_marks() {
+        assert_eq!(
+            number("SELECT '?', \"a?\" FROM t WHERE b = ? AND c = 'it''s ?';"),
+            "SELECT '?', \"a?\" FROM t WHERE b = $1 AND c = 'it''s ?';"
+        );
+    }
+}
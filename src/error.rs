@@ -0,0 +1,146 @@
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+use axum::{
+    http::{header::WWW_AUTHENTICATE, StatusCode},
+    response::{IntoResponse, Response},
+};
+use sqlx::error::DatabaseError;
+use tokio::sync::broadcast::error::SendError;
+use tracing::{error, warn};
+
+use crate::services::authenticator::AuthenticatorService;
+
+#[derive(Debug)]
+pub enum AppError {
+    Unauthorized,
+    NotFound,
+    Conflict(Box<dyn DatabaseError>),
+    Internal(Box<dyn Error + Send + Sync>),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AppErrorMessage(pub &'static str);
+
+impl AppError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::Conflict(..) => StatusCode::CONFLICT,
+            Self::Internal(..) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::Unauthorized => "error-unauthorized",
+            Self::NotFound => "error-not-found",
+            Self::Conflict(..) => "error-conflict",
+            Self::Internal(..) => "error-internal",
+        }
+    }
+}
+
+impl Display for AppError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unauthorized => write!(formatter, "unauthorized"),
+            Self::NotFound => write!(formatter, "not found"),
+            Self::Conflict(error) => Display::fmt(error, formatter),
+            Self::Internal(error) => Display::fmt(error, formatter),
+        }
+    }
+}
+
+impl Error for AppError {}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Internal(..) => error!("{self}"),
+            Self::Unauthorized | Self::NotFound | Self::Conflict(..) => {
+                warn!("{self}")
+            }
+        }
+        let mut response = match self {
+            Self::Unauthorized => {
+                (self.status(), [(WWW_AUTHENTICATE, "Bearer")]).into_response()
+            }
+            _ => self.status().into_response(),
+        };
+        response
+            .extensions_mut()
+            .insert(AppErrorMessage(self.message()));
+        response
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::Database(error)
+                if error.is_unique_violation()
+                    || error.is_foreign_key_violation() =>
+            {
+                Self::Conflict(error)
+            }
+            sqlx::Error::RowNotFound => Self::NotFound,
+            error => Self::Internal(Box::new(error)),
+        }
+    }
+}
+
+impl From<askama::Error> for AppError {
+    fn from(error: askama::Error) -> Self {
+        Self::Internal(Box::new(error))
+    }
+}
+
+impl From<axum_login::Error<AuthenticatorService>> for AppError {
+    fn from(error: axum_login::Error<AuthenticatorService>) -> Self {
+        Self::Internal(Box::new(error))
+    }
+}
+
+impl<T: Debug + Send + Sync + 'static> From<SendError<T>> for AppError {
+    fn from(error: SendError<T>) -> Self {
+        Self::Internal(Box::new(error))
+    }
+}
+
+impl From<&'static str> for AppError {
+    fn from(error: &'static str) -> Self {
+        Self::Internal(error.into())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use sqlx::query;
+
+    use super::*;
+    use crate::services::migrator::MigratorService;
+
+    #[tokio::test]
+    async fn conflicts_only_on_constraint_violations(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let database = sqlx::pool::PoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await?;
+        MigratorService::migrate(&database, true).await?;
+        let insert = "INSERT INTO users (name, password) VALUES ('bob', '');";
+        query(insert).execute(&database).await?;
+        let duplicate = query(insert).execute(&database).await.unwrap_err();
+        assert!(matches!(AppError::from(duplicate), AppError::Conflict(..)));
+        let missing = query("SELECT * FROM missing;")
+            .execute(&database)
+            .await
+            .unwrap_err();
+        assert!(matches!(AppError::from(missing), AppError::Internal(..)));
+        Ok(())
+    }
+}
@@ -1,22 +1,24 @@
+mod commands;
+mod config;
 mod controllers;
+mod database;
+mod error;
+mod markdown;
 mod models;
 mod routes;
 mod services;
 mod templates;
+mod validation;
 
-use std::{env::var, error::Error, sync::Arc};
+use std::error::Error;
 
-use axum::{extract::Request, middleware::from_fn, serve};
-use axum_csrf::{CsrfConfig, CsrfLayer};
+use clap::Parser;
+use commands::Cli;
+use config::{Config, LogFormat};
 use dotenvy::dotenv;
-use routes::routes;
-use services::{
-    authenticator::AuthenticatorService, integrity::integrity_service,
-    state::StateService,
-};
-use tokio::{main, net::TcpListener, signal::ctrl_c};
-use tower_http::{services::ServeDir, trace::TraceLayer};
-use tracing::{info, span, Level};
+#[cfg(feature = "otel")]
+use services::telemetry::TelemetryService;
+use tokio::main;
 use tracing_subscriber::{
     fmt::{self, format::FmtSpan},
     layer::SubscriberExt,
@@ -27,35 +29,30 @@ use tracing_subscriber::{
 
 #[main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    dotenv()?;
-    registry()
+    dotenv().ok();
+    let cli = Cli::parse();
+    let config = Config::load()?;
+    #[cfg(feature = "otel")]
+    let telemetry = cli.serves().then(TelemetryService::install).transpose()?;
+    let json = config.log_format == LogFormat::Json;
+    let registry = registry()
         .with(EnvFilter::try_from_default_env()?)
-        .with(fmt::layer().with_span_events(FmtSpan::NEW | FmtSpan::CLOSE))
-        .init();
-    let listener = TcpListener::bind(&var("APP_ADDRESS")?).await?;
-    info!("{listener:?}");
-    let state = Arc::new(StateService::new(&var("DATABASE_URL")?).await?);
-    info!("{state:?}");
-    serve(
-        listener,
-        routes()
-            .layer(AuthenticatorService::new(state.database().clone()).await?)
-            .layer(from_fn(integrity_service))
-            .layer(CsrfLayer::new(CsrfConfig::default()))
-            .layer(TraceLayer::new_for_http().make_span_with(
-                |request: &Request| {
-                    span! {
-                        Level::INFO,
-                        "request",
-                        method = %request.method(),
-                        route = %request.uri(),
-                    }
-                },
-            ))
-            .with_state(state)
-            .nest_service("/assets", ServeDir::new("./assets/")),
-    )
-    .with_graceful_shutdown(async { ctrl_c().await.unwrap() })
-    .await?;
-    Ok(())
+        .with((!json).then(|| {
+            fmt::layer().with_span_events(FmtSpan::NEW | FmtSpan::CLOSE)
+        }))
+        .with(json.then(|| {
+            fmt::layer()
+                .json()
+                .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE)
+        }));
+    #[cfg(feature = "otel")]
+    let registry =
+        registry.with(telemetry.as_ref().map(TelemetryService::layer));
+    registry.init();
+    let result = cli.run(config).await;
+    #[cfg(feature = "otel")]
+    if let Some(telemetry) = telemetry {
+        telemetry.shutdown()?;
+    }
+    result
 }
@@ -0,0 +1,251 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::LazyLock,
+};
+
+use ammonia::Builder;
+use pulldown_cmark::{
+    html::push_html, CowStr, Event, Options, Parser, Tag, TagEnd,
+    TextMergeStream,
+};
+use tracing::instrument;
+
+const TAGS: [&str; 24] = [
+    "a",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "strong",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+];
+
+static SANITIZER: LazyLock<Builder<'static>> = LazyLock::new(|| {
+    let mut builder = Builder::empty();
+    builder
+        .tags(HashSet::from(TAGS))
+        .tag_attributes(HashMap::from([
+            ("a", HashSet::from(["href", "title", "target", "rel"])),
+            ("ol", HashSet::from(["start"])),
+        ]))
+        .allowed_classes(HashMap::from([(
+            "a",
+            HashSet::from(["mention", "fw-bold", "link-warning"]),
+        )]))
+        .generic_attributes(HashSet::new())
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .link_rel(None);
+    builder
+});
+
+#[instrument(level = "trace", skip(source))]
+pub fn render(source: &str, mentions: &[String]) -> String {
+    let mut depth = 0;
+    let parser = events(source).flat_map(|event| {
+        match &event {
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                depth += 1;
+                if external(dest_url) {
+                    return vec![blank(event)];
+                }
+            }
+            Event::Start(Tag::CodeBlock(..)) => depth += 1,
+            Event::End(TagEnd::CodeBlock | TagEnd::Link) => depth -= 1,
+            Event::Text(text) if depth == 0 => return link(text, mentions),
+            _ => {}
+        }
+        vec![event]
+    });
+    let mut html = String::new();
+    push_html(&mut html, parser);
+    SANITIZER.clean(&html).to_string()
+}
+
+#[instrument(level = "trace", skip(source))]
+pub fn mentions(source: &str) -> Vec<String> {
+    let mut depth = 0;
+    let mut mentions = Vec::new();
+    for event in events(source) {
+        match &event {
+            Event::Start(Tag::CodeBlock(..) | Tag::Link { .. }) => depth += 1,
+            Event::End(TagEnd::CodeBlock | TagEnd::Link) => depth -= 1,
+            Event::Text(text) if depth == 0 => mentions.extend(
+                scan(text).map(|(start, end)| text[start + 1..end].to_owned()),
+            ),
+            _ => {}
+        }
+    }
+    mentions.sort();
+    mentions.dedup();
+    mentions
+}
+
+fn events(source: &str) -> impl Iterator<Item = Event<'_>> {
+    let parser = Parser::new_ext(
+        source,
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES,
+    )
+    .map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }),
+        Event::End(TagEnd::Image) => Event::End(TagEnd::Link),
+        event => event,
+    });
+    TextMergeStream::new(parser)
+}
+
+fn external(url: &str) -> bool {
+    let url = url.to_ascii_lowercase();
+    ["http://", "https://", "//"]
+        .iter()
+        .any(|prefix| url.starts_with(prefix))
+}
+
+fn blank(link: Event<'_>) -> Event<'_> {
+    let mut html = String::new();
+    push_html(&mut html, std::iter::once(link));
+    html.pop();
+    html.push_str(" target=\"_blank\" rel=\"noopener noreferrer nofollow\">");
+    Event::Html(CowStr::from(html))
+}
+
+fn link<'a>(text: &str, mentions: &[String]) -> Vec<Event<'a>> {
+    let mut events = Vec::new();
+    let mut rest = 0;
+    for (start, end) in scan(text) {
+        let name = &text[start + 1..end];
+        if mentions.iter().any(|mention| mention == name) {
+            events
+                .push(Event::Text(CowStr::from(text[rest..start].to_owned())));
+            events.push(Event::InlineHtml(CowStr::from(format!(
+                concat!(
+                    "<a href=\"/conversations?with={name}\" ",
+                    "class=\"mention fw-bold link-warning\">@{name}</a>",
+                ),
+                name = name,
+            ))));
+            rest = end;
+        }
+    }
+    events.push(Event::Text(CowStr::from(text[rest..].to_owned())));
+    events
+}
+
+fn scan(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let name = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_');
+    text.char_indices()
+        .filter(move |(start, c)| {
+            *c == '@' && !text[..*start].chars().next_back().is_some_and(name)
+        })
+        .filter_map(move |(start, _)| {
+            let end = text[start + 1..]
+                .char_indices()
+                .find(|(_, c)| !name(*c))
+                .map_or(text.len(), |(index, _)| start + 1 + index);
+            (start + 1 < end).then_some((start, end))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_commonmark() {
+        assert_eq!(
+            render("# Title\n\n*a* **b** ~~c~~ `d`", &[]),
+            "<h1>Title</h1>\n<p><em>a</em> <strong>b</strong> <del>c</del> \
+             <code>d</code></p>\n"
+        );
+        assert_eq!(
+            render("3. a\n4. b", &[]),
+            "<ol start=\"3\">\n<li>a</li>\n<li>b</li>\n</ol>\n"
+        );
+    }
+
+    #[test]
+    fn escapes_raw_html() {
+        assert_eq!(
+            render("<script>alert(1)</script>", &[]),
+            "&lt;script&gt;alert(1)&lt;/script&gt;"
+        );
+        assert_eq!(
+            render("a <img src=x onerror=alert(1)> b", &[]),
+            "<p>a &lt;img src=x onerror=alert(1)&gt; b</p>\n"
+        );
+    }
+
+    #[test]
+    fn secures_links() {
+        assert_eq!(
+            render("[a](https://example.com \"b\")", &[]),
+            "<p><a href=\"https://example.com\" title=\"b\" \
+             target=\"_blank\" rel=\"noopener noreferrer nofollow\">a</a></p>\n"
+        );
+        assert_eq!(
+            render("[a](javascript:alert(1))", &[]),
+            "<p><a>a</a></p>\n"
+        );
+        assert_eq!(
+            render("[a](/conversations) [b](mailto:b@example.com)", &[]),
+            "<p><a href=\"/conversations\">a</a> \
+             <a href=\"mailto:b@example.com\">b</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn links_images() {
+        assert_eq!(
+            render("![a](https://example.com/a.png)", &[]),
+            "<p><a href=\"https://example.com/a.png\" \
+             target=\"_blank\" rel=\"noopener noreferrer nofollow\">a</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn finds_mentions() {
+        assert_eq!(
+            mentions("@bob hi @alice, @bob! a@b.c `@carol`\n\n    @dave"),
+            ["alice", "bob"]
+        );
+        assert_eq!(mentions("[@erin](https://example.com) @ @-x_"), ["-x_"]);
+    }
+
+    #[test]
+    fn links_mentions() {
+        let mentions = ["bob".to_owned()];
+        assert_eq!(
+            render("hi @bob and @alice", &mentions),
+            "<p>hi <a href=\"/conversations?with=bob\" \
+             class=\"mention fw-bold link-warning\">@bob</a> and @alice</p>\n"
+        );
+        assert_eq!(render("`@bob`", &mentions), "<p><code>@bob</code></p>\n");
+    }
+}
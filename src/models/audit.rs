@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{query, query_as, Error, FromRow};
+use time::{
+    format_description::well_known::Rfc3339, macros::format_description, Date,
+    Duration, OffsetDateTime,
+};
+use tracing::instrument;
+use validator::{Validate, ValidationError};
+
+use crate::{
+    config::Config,
+    database::{sql, DatabasePool, DatabaseQueryResult},
+};
+
+#[cfg(not(feature = "sqlite"))]
+const FILTER: &str = "SELECT * FROM audit_events
+    WHERE (? IS NULL OR actor = ?)
+    AND (? IS NULL OR action = ?)
+    AND created_at >= ? AND created_at < ?
+    ORDER BY id DESC
+    LIMIT ?;";
+
+#[cfg(feature = "sqlite")]
+const FILTER: &str = "SELECT * FROM audit_events
+    WHERE (? IS NULL OR actor = ?)
+    AND (? IS NULL OR action = ?)
+    AND created_at >= datetime(?) AND created_at < datetime(?)
+    ORDER BY id DESC
+    LIMIT ?;";
+
+#[derive(Clone, Debug, FromRow, Serialize)]
+pub struct AuditEventModel {
+    pub id: i32,
+    pub actor: Option<String>,
+    pub action: String,
+    pub target: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Default, Deserialize, Validate)]
+#[validate(context = Config)]
+pub struct AuditEventFilter {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    #[validate(custom(function = date))]
+    pub from: Option<String>,
+    #[validate(custom(function = date))]
+    pub to: Option<String>,
+}
+
+impl AuditEventModel {
+    pub const LOGIN: &'static str = "login";
+    pub const LOGIN_FAILED: &'static str = "login_failed";
+    pub const LOGOUT: &'static str = "logout";
+    pub const REGISTER: &'static str = "register";
+    pub const MESSAGE_CREATE: &'static str = "message_create";
+    pub const MESSAGE_UPDATE: &'static str = "message_update";
+    pub const MESSAGE_DELETE: &'static str = "message_delete";
+    pub const MESSAGE_PIN: &'static str = "message_pin";
+    pub const MESSAGE_UNPIN: &'static str = "message_unpin";
+    pub const CHANNEL_CREATE: &'static str = "channel_create";
+    pub const CHANNEL_ADD_MEMBER: &'static str = "channel_add_member";
+    pub const USER_CREATE: &'static str = "user_create";
+    pub const USER_SET_PASSWORD: &'static str = "user_set_password";
+    pub const USER_SET_ROLE: &'static str = "user_set_role";
+    pub const USER_DISABLE: &'static str = "user_disable";
+    pub const USER_ENABLE: &'static str = "user_enable";
+    pub const USER_ISSUE_TOKEN: &'static str = "user_issue_token";
+    pub const USER_REVOKE_TOKEN: &'static str = "user_revoke_token";
+
+    #[instrument(level = "trace")]
+    pub async fn filter(
+        database: &DatabasePool,
+        filter: &AuditEventFilter,
+        limit: Option<i64>,
+    ) -> Result<Vec<Self>, Error> {
+        let actor = filter.actor();
+        let action = filter.action();
+        let (from, to) = filter.range();
+        query_as(&sql(FILTER))
+            .bind(actor)
+            .bind(actor)
+            .bind(action)
+            .bind(action)
+            .bind(from)
+            .bind(to)
+            .bind(limit.unwrap_or(i64::MAX))
+            .fetch_all(database)
+            .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn create(
+        database: &DatabasePool,
+        actor: Option<&str>,
+        action: &str,
+        target: Option<&str>,
+        ip: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(
+            "INSERT INTO audit_events (actor, action, target, ip, user_agent)
+             VALUES (?, ?, ?, ?, ?);",
+        ))
+        .bind(actor)
+        .bind(action)
+        .bind(target)
+        .bind(ip)
+        .bind(user_agent)
+        .execute(database)
+        .await
+    }
+
+    pub const ACTIONS: [&'static str; 18] = [
+        Self::LOGIN,
+        Self::LOGIN_FAILED,
+        Self::LOGOUT,
+        Self::REGISTER,
+        Self::MESSAGE_CREATE,
+        Self::MESSAGE_UPDATE,
+        Self::MESSAGE_DELETE,
+        Self::MESSAGE_PIN,
+        Self::MESSAGE_UNPIN,
+        Self::CHANNEL_CREATE,
+        Self::CHANNEL_ADD_MEMBER,
+        Self::USER_CREATE,
+        Self::USER_SET_PASSWORD,
+        Self::USER_SET_ROLE,
+        Self::USER_DISABLE,
+        Self::USER_ENABLE,
+        Self::USER_ISSUE_TOKEN,
+        Self::USER_REVOKE_TOKEN,
+    ];
+
+    #[instrument(level = "trace", skip(events))]
+    pub fn csv(events: &[Self]) -> String {
+        let mut csv =
+            String::from("id,actor,action,target,ip,user_agent,created_at\r\n");
+        for event in events {
+            let fields = [
+                event.id.to_string(),
+                event.actor.clone().unwrap_or_default(),
+                event.action.clone(),
+                event.target.clone().unwrap_or_default(),
+                event.ip.clone().unwrap_or_default(),
+                event.user_agent.clone().unwrap_or_default(),
+                event.timestamp(),
+            ];
+            csv.push_str(
+                &fields
+                    .iter()
+                    .map(|field| escape(field))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+            csv.push_str("\r\n");
+        }
+        csv
+    }
+
+    pub fn timestamp(&self) -> String {
+        self.created_at
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| self.created_at.to_string())
+    }
+}
+
+impl AuditEventFilter {
+    fn actor(&self) -> Option<&str> {
+        self.actor.as_deref().filter(|actor| !actor.is_empty())
+    }
+
+    fn action(&self) -> Option<&str> {
+        self.action.as_deref().filter(|action| !action.is_empty())
+    }
+
+    fn range(&self) -> (OffsetDateTime, OffsetDateTime) {
+        let parse = |date: &Option<String>| {
+            date.as_deref().and_then(|date| parse(date).ok().flatten())
+        };
+        (
+            parse(&self.from)
+                .map(|from| from.midnight().assume_utc())
+                .unwrap_or(OffsetDateTime::UNIX_EPOCH),
+            parse(&self.to)
+                .map(|to| to.midnight().assume_utc() + Duration::days(1))
+                .unwrap_or_else(|| {
+                    OffsetDateTime::now_utc() + Duration::days(1)
+                }),
+        )
+    }
+}
+
+fn parse(date: &str) -> Result<Option<Date>, time::error::Parse> {
+    if date.is_empty() {
+        return Ok(None);
+    }
+    Date::parse(date, format_description!("[year]-[month]-[day]")).map(Some)
+}
+
+fn date(date: &str) -> Result<(), ValidationError> {
+    parse(date)
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("date"))
+}
+
+fn escape(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{field}")
+    } else {
+        field.to_owned()
+    };
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::{
+        services::i18n::{I18nService, LocaleService},
+        validation::validate,
+    };
+
+    fn filter(from: &str, to: &str) -> AuditEventFilter {
+        AuditEventFilter {
+            from: Some(from.to_owned()),
+            to: Some(to.to_owned()),
+            ..AuditEventFilter::default()
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        let config = Config::test();
+        let locale = LocaleService::new(
+            Arc::new(I18nService::new().unwrap()),
+            None,
+            None,
+        );
+        assert!(validate(&filter("", ""), &config, &locale).is_ok());
+        assert!(validate(
+            &filter("2026-10-01", "2026-10-19"),
+            &config,
+            &locale
+        )
+        .is_ok());
+        let errors =
+            validate(&filter("2026-13-01", "yesterday"), &config, &locale)
+                .unwrap_err();
+        assert_eq!(errors.get("from"), Some("From is invalid."));
+        assert_eq!(errors.get("to"), Some("To is invalid."));
+    }
+
+    #[test]
+    fn includes_the_whole_to_day() {
+        let (from, to) = filter("2026-10-01", "2026-10-19").range();
+        assert_eq!(from, datetime!(2026-10-01 0:00 UTC));
+        assert_eq!(to, datetime!(2026-10-20 0:00 UTC));
+        assert!(datetime!(2026-10-19 23:59:59 UTC) < to);
+    }
+}
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{query, query_as, query_scalar, Error, FromRow};
+use tracing::{error, instrument};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+use crate::{
+    config::Config,
+    database::{sql, DatabasePool, DatabaseQueryResult},
+    services::i18n::LocaleService,
+    validation::{max_length, not_blank, not_empty, validate, FieldErrors},
+};
+
+pub const NAME_LENGTH: usize = 50;
+
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize, ToSchema, Validate)]
+#[validate(context = Config)]
+pub struct ChannelModel {
+    #[schema(read_only)]
+    pub id: Option<i32>,
+    #[validate(
+        custom(function = not_empty),
+        custom(function = not_blank),
+        custom(function = name_max)
+    )]
+    pub name: String,
+    #[serde(default)]
+    pub private: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ChannelQuery {
+    pub channel: Option<i32>,
+}
+
+impl ChannelModel {
+    pub const GENERAL: i32 = 1;
+
+    #[instrument(level = "trace")]
+    pub async fn find_by_name(
+        database: &DatabasePool,
+        name: &str,
+    ) -> Result<Option<Self>, Error> {
+        query_as(&sql("SELECT * FROM channels WHERE name = ? LIMIT 1;"))
+            .bind(name)
+            .fetch_optional(database)
+            .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn accessible(
+        database: &DatabasePool,
+        id: i32,
+        user: &str,
+    ) -> Result<Option<Self>, Error> {
+        query_as(&sql(concat!(
+            "SELECT * FROM channels WHERE id = ? AND (private = FALSE OR ",
+            "id IN (SELECT channel_id FROM channel_members WHERE user_name = ?)",
+            ") LIMIT 1;",
+        )))
+        .bind(id)
+        .bind(user)
+        .fetch_optional(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn visible(
+        database: &DatabasePool,
+        user: &str,
+    ) -> Result<Vec<Self>, Error> {
+        query_as(&sql(concat!(
+            "SELECT * FROM channels WHERE private = FALSE OR id IN (",
+            "SELECT channel_id FROM channel_members WHERE user_name = ?",
+            ") ORDER BY name;",
+        )))
+        .bind(user)
+        .fetch_all(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn members(
+        database: &DatabasePool,
+        id: i32,
+    ) -> Result<Vec<String>, Error> {
+        query_scalar(&sql(concat!(
+            "SELECT user_name FROM channel_members WHERE channel_id = ? ",
+            "ORDER BY user_name;",
+        )))
+        .bind(id)
+        .fetch_all(database)
+        .await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[instrument(level = "trace")]
+    pub async fn create(
+        database: &DatabasePool,
+        name: &str,
+        private: bool,
+    ) -> Result<i32, Error> {
+        let id =
+            query(&sql("INSERT INTO channels (name, private) VALUES (?, ?);"))
+                .bind(name)
+                .bind(private)
+                .execute(database)
+                .await?
+                .last_insert_id();
+        i32::try_from(id).map_err(|error| Error::Decode(Box::new(error)))
+    }
+
+    #[cfg(not(feature = "mysql"))]
+    #[instrument(level = "trace")]
+    pub async fn create(
+        database: &DatabasePool,
+        name: &str,
+        private: bool,
+    ) -> Result<i32, Error> {
+        query_scalar(&sql(
+            "INSERT INTO channels (name, private) VALUES (?, ?) RETURNING id;",
+        ))
+        .bind(name)
+        .bind(private)
+        .fetch_one(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn add_member(
+        database: &DatabasePool,
+        id: i32,
+        user: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(concat!(
+            "INSERT INTO channel_members (channel_id, user_name) ",
+            "VALUES (?, ?);",
+        )))
+        .bind(id)
+        .bind(user)
+        .execute(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn validate(
+        database: &DatabasePool,
+        config: &Config,
+        locale: &LocaleService,
+        channel: &Self,
+    ) -> Result<(), FieldErrors> {
+        let mut errors =
+            validate(channel, config, locale).err().unwrap_or_default();
+        if errors.get("name").is_none() {
+            match Self::find_by_name(database, &channel.name).await {
+                Ok(Some(..)) => {
+                    errors.insert("name", locale.get("validation-taken"));
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    error!("{error}");
+                    errors.insert("name", locale.get("validation-internal"));
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn name_max(name: &str) -> Result<(), ValidationError> {
+    max_length(name, NAME_LENGTH)
+}
+
+impl ChannelQuery {
+    pub fn id(&self) -> i32 {
+        self.channel.unwrap_or(ChannelModel::GENERAL)
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use sqlx::{query, query_as, query_scalar, Error, FromRow, Transaction};
+use tracing::instrument;
+
+use crate::database::{sql, Database, DatabasePool, DatabaseQueryResult};
+
+#[derive(Clone, Debug, FromRow)]
+pub struct ConversationModel {
+    pub id: i32,
+    pub unread: i64,
+    #[sqlx(skip)]
+    pub participants: Vec<String>,
+}
+
+macro_rules! select {
+    ($($query:literal),+ $(,)?) => {
+        concat!(
+            "SELECT conversation_id AS id, (",
+            "SELECT COUNT(*) FROM direct_messages ",
+            "WHERE direct_messages.conversation_id = ",
+            "conversation_participants.conversation_id ",
+            "AND direct_messages.id > conversation_participants.last_read ",
+            "AND direct_messages.sender <> conversation_participants.user_name",
+            ") AS unread FROM conversation_participants ",
+            $($query),+
+        )
+    };
+}
+
+impl ConversationModel {
+    pub const PARTICIPANTS_MAX: usize = 8;
+
+    #[instrument(level = "trace")]
+    pub async fn find(
+        database: &DatabasePool,
+        id: i32,
+        user: &str,
+    ) -> Result<Option<Self>, Error> {
+        let conversation: Option<Self> = query_as(&sql(select!(
+            "WHERE conversation_id = ? AND user_name = ? LIMIT 1;",
+        )))
+        .bind(id)
+        .bind(user)
+        .fetch_optional(database)
+        .await?;
+        let Some(mut conversation) = conversation else {
+            return Ok(None);
+        };
+        conversation.participants = Self::participants(database, id).await?;
+        Ok(Some(conversation))
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn list(
+        database: &DatabasePool,
+        user: &str,
+    ) -> Result<Vec<Self>, Error> {
+        let mut conversations: Vec<Self> = query_as(&sql(select!(
+            "WHERE user_name = ? ORDER BY (",
+            "SELECT COALESCE(MAX(id), 0) FROM direct_messages ",
+            "WHERE direct_messages.conversation_id = ",
+            "conversation_participants.conversation_id",
+            ") DESC, conversation_id DESC;",
+        )))
+        .bind(user)
+        .fetch_all(database)
+        .await?;
+        let mut participants = HashMap::<i32, Vec<String>>::new();
+        let rows: Vec<(i32, String)> = query_as(&sql(concat!(
+            "SELECT conversation_id, user_name FROM conversation_participants ",
+            "WHERE conversation_id IN (SELECT conversation_id ",
+            "FROM conversation_participants WHERE user_name = ?) ",
+            "ORDER BY user_name;",
+        )))
+        .bind(user)
+        .fetch_all(database)
+        .await?;
+        for (id, name) in rows {
+            participants.entry(id).or_default().push(name);
+        }
+        for conversation in &mut conversations {
+            conversation.participants =
+                participants.remove(&conversation.id).unwrap_or_default();
+        }
+        Ok(conversations)
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn participants(
+        database: &DatabasePool,
+        id: i32,
+    ) -> Result<Vec<String>, Error> {
+        query_scalar(&sql(concat!(
+            "SELECT user_name FROM conversation_participants ",
+            "WHERE conversation_id = ? ORDER BY user_name;",
+        )))
+        .bind(id)
+        .fetch_all(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn unread(
+        database: &DatabasePool,
+        user: &str,
+    ) -> Result<i64, Error> {
+        query_scalar(&sql(concat!(
+            "SELECT COUNT(*) FROM direct_messages ",
+            "JOIN conversation_participants ON ",
+            "conversation_participants.conversation_id = ",
+            "direct_messages.conversation_id ",
+            "WHERE conversation_participants.user_name = ? ",
+            "AND direct_messages.id > conversation_participants.last_read ",
+            "AND direct_messages.sender <> conversation_participants.user_name;",
+        )))
+        .bind(user)
+        .fetch_one(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn create(
+        database: &DatabasePool,
+        participants: &[String],
+    ) -> Result<i32, Error> {
+        let mut transaction = database.begin().await?;
+        let id = Self::insert(&mut transaction).await?;
+        for participant in participants {
+            query(&sql(concat!(
+                "INSERT INTO conversation_participants ",
+                "(conversation_id, user_name) VALUES (?, ?);",
+            )))
+            .bind(id)
+            .bind(participant)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await?;
+        Ok(id)
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn read(
+        database: &DatabasePool,
+        id: i32,
+        user: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(concat!(
+            "UPDATE conversation_participants SET last_read = (",
+            "SELECT COALESCE(MAX(id), 0) FROM direct_messages ",
+            "WHERE conversation_id = ?",
+            ") WHERE conversation_id = ? AND user_name = ?;",
+        )))
+        .bind(id)
+        .bind(id)
+        .bind(user)
+        .execute(database)
+        .await
+    }
+
+    #[cfg(feature = "mysql")]
+    async fn insert(
+        transaction: &mut Transaction<'_, Database>,
+    ) -> Result<i32, Error> {
+        let id = query("INSERT INTO conversations () VALUES ();")
+            .execute(&mut **transaction)
+            .await?
+            .last_insert_id();
+        i32::try_from(id).map_err(|error| Error::Decode(Box::new(error)))
+    }
+
+    #[cfg(not(feature = "mysql"))]
+    async fn insert(
+        transaction: &mut Transaction<'_, Database>,
+    ) -> Result<i32, Error> {
+        query_scalar("INSERT INTO conversations DEFAULT VALUES RETURNING id;")
+            .fetch_one(&mut **transaction)
+            .await
+    }
+}
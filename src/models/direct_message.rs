@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{query_as, Error, FromRow};
+use tracing::instrument;
+use validator::{Validate, ValidationError};
+
+#[cfg(feature = "mysql")]
+use sqlx::query;
+#[cfg(not(feature = "mysql"))]
+use sqlx::query_scalar;
+
+use crate::{
+    config::Config,
+    database::{sql, DatabasePool},
+    validation::{max_length, not_blank, not_empty},
+};
+
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize, Validate)]
+#[validate(context = Config)]
+pub struct DirectMessageModel {
+    pub id: Option<i32>,
+    #[serde(default)]
+    pub conversation_id: i32,
+    #[serde(default)]
+    pub sender: String,
+    #[validate(
+        custom(function = not_empty),
+        custom(function = not_blank),
+        custom(function = content_max, use_context)
+    )]
+    pub content: String,
+}
+
+impl DirectMessageModel {
+    #[instrument(level = "trace")]
+    pub async fn list(
+        database: &DatabasePool,
+        conversation_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        query_as(&sql(
+            "SELECT * FROM direct_messages WHERE conversation_id = ? ORDER BY id;",
+        ))
+        .bind(conversation_id)
+        .fetch_all(database)
+        .await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[instrument(level = "trace")]
+    pub async fn create(
+        database: &DatabasePool,
+        conversation_id: i32,
+        sender: &str,
+        content: &str,
+    ) -> Result<i32, Error> {
+        let id = query(&sql(concat!(
+            "INSERT INTO direct_messages (conversation_id, sender, content) ",
+            "VALUES (?, ?, ?);",
+        )))
+        .bind(conversation_id)
+        .bind(sender)
+        .bind(content)
+        .execute(database)
+        .await?
+        .last_insert_id();
+        i32::try_from(id).map_err(|error| Error::Decode(Box::new(error)))
+    }
+
+    #[cfg(not(feature = "mysql"))]
+    #[instrument(level = "trace")]
+    pub async fn create(
+        database: &DatabasePool,
+        conversation_id: i32,
+        sender: &str,
+        content: &str,
+    ) -> Result<i32, Error> {
+        query_scalar(&sql(concat!(
+            "INSERT INTO direct_messages (conversation_id, sender, content) ",
+            "VALUES (?, ?, ?) RETURNING id;",
+        )))
+        .bind(conversation_id)
+        .bind(sender)
+        .bind(content)
+        .fetch_one(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub fn sanitize(self) -> Self {
+        Self {
+            content: self
+                .content
+                .chars()
+                .filter(|c| matches!(c, '\n' | '\t') || !c.is_control())
+                .collect(),
+            ..self
+        }
+    }
+}
+
+fn content_max(content: &str, config: &Config) -> Result<(), ValidationError> {
+    max_length(content, config.messages_content_max)
+}
@@ -0,0 +1,100 @@
+use sqlx::{query, query_scalar, Error, FromRow, QueryBuilder};
+use tracing::instrument;
+
+use crate::{
+    database::{sql, DatabasePool},
+    markdown,
+    models::user::UserModel,
+};
+
+#[derive(Clone, Debug, FromRow)]
+pub struct MentionModel {
+    pub message_id: i32,
+    pub user_name: String,
+}
+
+impl MentionModel {
+    pub const MENTIONS_MAX: usize = 10;
+
+    #[instrument(level = "trace")]
+    pub async fn message(
+        database: &DatabasePool,
+        message_id: i32,
+    ) -> Result<Vec<String>, Error> {
+        query_scalar(&sql(concat!(
+            "SELECT user_name FROM message_mentions WHERE message_id = ? ",
+            "ORDER BY user_name;",
+        )))
+        .bind(message_id)
+        .fetch_all(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn messages(
+        database: &DatabasePool,
+        message_ids: &[i32],
+    ) -> Result<Vec<Self>, Error> {
+        if message_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut query = QueryBuilder::new(
+            "SELECT * FROM message_mentions WHERE message_id IN (",
+        );
+        let mut ids = query.separated(", ");
+        for id in message_ids {
+            ids.push_bind(*id);
+        }
+        ids.push_unseparated(") ORDER BY user_name;");
+        query.build_query_as().fetch_all(database).await
+    }
+
+    #[instrument(level = "trace", skip(content))]
+    pub async fn resolve(
+        database: &DatabasePool,
+        content: &str,
+    ) -> Result<Vec<String>, Error> {
+        let mut mentions = Vec::new();
+        for name in markdown::mentions(content) {
+            if Self::MENTIONS_MAX <= mentions.len() {
+                break;
+            }
+            if let Some(user) = UserModel::find(database, &name).await? {
+                mentions.push(user.name);
+            }
+        }
+        Ok(mentions)
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn set(
+        database: &DatabasePool,
+        message_id: i32,
+        names: &[String],
+    ) -> Result<(), Error> {
+        let mut transaction = database.begin().await?;
+        query(&sql("DELETE FROM message_mentions WHERE message_id = ?;"))
+            .bind(message_id)
+            .execute(&mut *transaction)
+            .await?;
+        for name in names {
+            query(&sql(concat!(
+                "INSERT INTO message_mentions (message_id, user_name) ",
+                "VALUES (?, ?);",
+            )))
+            .bind(message_id)
+            .bind(name)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await
+    }
+
+    pub fn names(mentions: &[Self], message_id: i32) -> Vec<String> {
+        mentions
+            .iter()
+            .filter(|mention| mention.message_id == message_id)
+            .map(|mention| mention.user_name.clone())
+            .collect()
+    }
+}
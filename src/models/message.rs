@@ -1,112 +1,536 @@
 use serde::{Deserialize, Serialize};
 use sqlx::{
-    mysql::MySqlQueryResult, query, query_as, Error, FromRow, MySqlPool,
+    query, query_as, query_scalar, Error, FromRow, QueryBuilder, Transaction,
 };
 use tracing::instrument;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
 
-#[derive(Clone, Debug, Deserialize, FromRow, Serialize)]
+use crate::{
+    config::Config,
+    database::{sql, Database, DatabasePool, DatabaseQueryResult},
+    models::{
+        channel::ChannelModel,
+        mention::MentionModel,
+        reaction::{ReactionCountModel, ReactionModel},
+        tag::{self, TagModel},
+    },
+    validation::{max_length, not_blank, not_empty},
+};
+
+pub const TITLE_LENGTH: usize = 100;
+pub const CONTENT_LENGTH: usize = 1000;
+
+macro_rules! select {
+    ($($query:literal),+ $(,)?) => {
+        concat!(
+            "SELECT id, channel_id, parent_id, author, title, content, (",
+            "SELECT COUNT(*) FROM messages AS replies ",
+            "WHERE replies.parent_id = messages.id",
+            ") AS replies FROM messages ",
+            $($query),+
+        )
+    };
+}
+
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize, ToSchema, Validate)]
+#[validate(context = Config)]
 pub struct MessageModel {
+    #[schema(read_only)]
     pub id: Option<i32>,
+    #[serde(default = "general")]
+    pub channel_id: i32,
+    pub parent_id: Option<i32>,
+    #[serde(default)]
+    #[schema(read_only)]
+    pub author: Option<String>,
+    #[validate(
+        custom(function = not_empty),
+        custom(function = not_blank),
+        custom(function = title_max, use_context)
+    )]
     pub title: String,
+    #[validate(
+        custom(function = not_empty),
+        custom(function = content_max, use_context)
+    )]
     pub content: String,
-}
-
-#[derive(Serialize)]
-pub struct MessageModelError {
-    pub title: Option<&'static str>,
-    pub content: Option<&'static str>,
+    #[serde(default)]
+    #[schema(read_only)]
+    pub replies: i64,
+    #[serde(default)]
+    #[sqlx(skip)]
+    #[schema(read_only)]
+    pub reactions: Vec<ReactionCountModel>,
+    #[serde(default, deserialize_with = "tag::deserialize")]
+    #[sqlx(skip)]
+    #[validate(custom(function = tag::validate))]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    #[sqlx(skip)]
+    #[schema(read_only)]
+    pub mentions: Vec<String>,
 }
 
 impl MessageModel {
     #[instrument(level = "trace")]
     pub async fn find(
-        database: &MySqlPool,
+        database: &DatabasePool,
         id: i32,
     ) -> Result<Option<Self>, Error> {
-        query_as!(Self, "SELECT * FROM messages WHERE id = ? LIMIT 1;", id)
-            .fetch_optional(database)
-            .await
+        let message: Option<Self> =
+            query_as(&sql(select!("WHERE id = ? LIMIT 1;")))
+                .bind(id)
+                .fetch_optional(database)
+                .await?;
+        let Some(mut message) = message else {
+            return Ok(None);
+        };
+        let reactions = ReactionModel::message(database, id).await?;
+        message.reactions = ReactionModel::counts(&reactions, id);
+        message.tags = TagModel::message(database, id).await?;
+        message.mentions = MentionModel::message(database, id).await?;
+        Ok(Some(message))
     }
 
     #[instrument(level = "trace")]
-    pub async fn all(database: &MySqlPool) -> Result<Vec<Self>, Error> {
-        query_as!(Self, "SELECT * FROM messages ORDER BY id DESC;")
-            .fetch_all(database)
-            .await
+    pub async fn roots(
+        database: &DatabasePool,
+        channel_id: i32,
+        tags: &[String],
+    ) -> Result<Vec<Self>, Error> {
+        let mut query = QueryBuilder::new(select!("WHERE channel_id = "));
+        query.push_bind(channel_id).push(" AND parent_id IS NULL");
+        TagModel::filter(&mut query, tags);
+        query.push(" ORDER BY id DESC;");
+        let messages = query.build_query_as().fetch_all(database).await?;
+        Self::annotate(database, messages).await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn pinned(
+        database: &DatabasePool,
+        channel_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        let messages = query_as(&sql(select!(
+            "WHERE channel_id = ? AND id IN ",
+            "(SELECT message_id FROM pinned_messages) ORDER BY id DESC;",
+        )))
+        .bind(channel_id)
+        .fetch_all(database)
+        .await?;
+        Self::annotate(database, messages).await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn starred(
+        database: &DatabasePool,
+        channel_id: i32,
+        user: &str,
+        tags: &[String],
+    ) -> Result<Vec<Self>, Error> {
+        let mut query = QueryBuilder::new(select!("WHERE channel_id = "));
+        query.push_bind(channel_id).push(concat!(
+            " AND id IN (SELECT message_id FROM starred_messages ",
+            "WHERE user_name = ",
+        ));
+        query.push_bind(user).push(")");
+        TagModel::filter(&mut query, tags);
+        query.push(" ORDER BY id DESC;");
+        let messages = query.build_query_as().fetch_all(database).await?;
+        Self::annotate(database, messages).await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn thread(
+        database: &DatabasePool,
+        id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        let messages: Vec<Self> = query_as(&sql(select!(
+            "WHERE id IN (WITH RECURSIVE thread (id) AS (",
+            "SELECT id FROM messages WHERE id = ? UNION ALL ",
+            "SELECT messages.id FROM messages ",
+            "JOIN thread ON messages.parent_id = thread.id",
+            ") SELECT id FROM thread) ORDER BY id;",
+        )))
+        .bind(id)
+        .fetch_all(database)
+        .await?;
+        Self::annotate(database, messages).await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn page(
+        database: &DatabasePool,
+        channel_id: i32,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, Error> {
+        let messages = query_as(&sql(select!(
+            "WHERE channel_id = ? ORDER BY id DESC LIMIT ? OFFSET ?;",
+        )))
+        .bind(channel_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(database)
+        .await?;
+        Self::annotate(database, messages).await
     }
 
     #[instrument(level = "trace")]
+    pub async fn count(
+        database: &DatabasePool,
+        channel_id: i32,
+    ) -> Result<i64, Error> {
+        query_scalar(&sql(
+            "SELECT COUNT(*) FROM messages WHERE channel_id = ?;",
+        ))
+        .bind(channel_id)
+        .fetch_one(database)
+        .await
+    }
+
+    #[cfg(feature = "mysql")]
+    #[instrument(level = "trace", skip(transaction))]
     pub async fn create(
-        database: &MySqlPool,
+        transaction: &mut Transaction<'_, Database>,
+        channel_id: i32,
+        parent_id: Option<i32>,
+        author: &str,
         title: &str,
         content: &str,
-    ) -> Result<MySqlQueryResult, Error> {
-        query!(
-            "INSERT INTO messages (title, content) VALUES (?, ?);",
-            title,
-            content
-        )
-        .execute(database)
+    ) -> Result<i32, Error> {
+        let id = query(&sql(concat!(
+            "INSERT INTO messages (channel_id, parent_id, author, title, ",
+            "content) VALUES (?, ?, ?, ?, ?);",
+        )))
+        .bind(channel_id)
+        .bind(parent_id)
+        .bind(author)
+        .bind(title)
+        .bind(content)
+        .execute(&mut **transaction)
+        .await?
+        .last_insert_id();
+        i32::try_from(id).map_err(|error| Error::Decode(Box::new(error)))
+    }
+
+    #[cfg(not(feature = "mysql"))]
+    #[instrument(level = "trace", skip(transaction))]
+    pub async fn create(
+        transaction: &mut Transaction<'_, Database>,
+        channel_id: i32,
+        parent_id: Option<i32>,
+        author: &str,
+        title: &str,
+        content: &str,
+    ) -> Result<i32, Error> {
+        query_scalar(&sql(concat!(
+            "INSERT INTO messages (channel_id, parent_id, author, title, ",
+            "content) VALUES (?, ?, ?, ?, ?) RETURNING id;",
+        )))
+        .bind(channel_id)
+        .bind(parent_id)
+        .bind(author)
+        .bind(title)
+        .bind(content)
+        .fetch_one(&mut **transaction)
         .await
     }
 
-    #[instrument(level = "trace")]
+    #[instrument(level = "trace", skip(transaction))]
     pub async fn update(
-        database: &MySqlPool,
+        transaction: &mut Transaction<'_, Database>,
         id: i32,
         title: &str,
         content: &str,
-    ) -> Result<MySqlQueryResult, Error> {
-        query!(
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(
             "UPDATE messages SET title = ?, content = ? WHERE id = ?;",
-            title,
-            content,
-            id
-        )
-        .execute(database)
+        ))
+        .bind(title)
+        .bind(content)
+        .bind(id)
+        .execute(&mut **transaction)
         .await
     }
 
     #[instrument(level = "trace")]
     pub async fn delete(
-        database: &MySqlPool,
+        database: &DatabasePool,
         id: i32,
-    ) -> Result<MySqlQueryResult, Error> {
-        query!("DELETE FROM messages WHERE id = ?;", id)
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql("DELETE FROM messages WHERE id = ?;"))
+            .bind(id)
             .execute(database)
             .await
     }
 
-    #[instrument(level = "trace")]
-    pub fn validate_title(title: &str) -> Option<&'static str> {
-        if title.is_empty() {
-            Some("Title must be at least 1 character long.")
-        } else if 100 < title.len() {
-            Some("Title must not be more than 100 characters long.")
-        } else {
-            None
+    pub fn reaction(&self, reaction: &str) -> Option<&ReactionCountModel> {
+        self.reactions
+            .iter()
+            .find(|count| count.reaction == reaction)
+    }
+
+    #[instrument(level = "trace", skip(messages))]
+    async fn annotate(
+        database: &DatabasePool,
+        mut messages: Vec<Self>,
+    ) -> Result<Vec<Self>, Error> {
+        let ids: Vec<i32> =
+            messages.iter().filter_map(|message| message.id).collect();
+        let reactions = ReactionModel::messages(database, &ids).await?;
+        let tags = TagModel::messages(database, &ids).await?;
+        let mentions = MentionModel::messages(database, &ids).await?;
+        for message in &mut messages {
+            if let Some(id) = message.id {
+                message.reactions = ReactionModel::counts(&reactions, id);
+                message.tags = TagModel::names(&tags, id);
+                message.mentions = MentionModel::names(&mentions, id);
+            }
         }
+        Ok(messages)
     }
 
     #[instrument(level = "trace")]
-    pub fn validate_content(content: &str) -> Option<&'static str> {
-        if content.is_empty() {
-            Some("Content must be at least 1 character long.")
-        } else if 100 < content.len() {
-            Some("Content must not be more than 1000 characters long.")
-        } else {
-            None
+    pub fn sanitize(self) -> Self {
+        Self {
+            id: self.id,
+            channel_id: self.channel_id,
+            parent_id: self.parent_id,
+            author: self.author,
+            title: self.title.chars().filter(|c| !c.is_control()).collect(),
+            content: self
+                .content
+                .chars()
+                .filter(|c| matches!(c, '\n' | '\t') || !c.is_control())
+                .collect(),
+            replies: self.replies,
+            reactions: self.reactions,
+            tags: TagModel::normalize(self.tags),
+            mentions: self.mentions,
         }
     }
+}
 
-    #[instrument(level = "trace")]
-    pub fn validate(message: &Self) -> Option<MessageModelError> {
-        let title = Self::validate_title(&message.title);
-        let content = Self::validate_content(&message.content);
-        if title.is_some() || content.is_some() {
-            Some(MessageModelError { title, content })
-        } else {
-            None
+fn general() -> i32 {
+    ChannelModel::GENERAL
+}
+
+fn title_max(title: &str, config: &Config) -> Result<(), ValidationError> {
+    max_length(title, config.messages_title_max)
+}
+
+fn content_max(content: &str, config: &Config) -> Result<(), ValidationError> {
+    max_length(content, config.messages_content_max)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    #[cfg(feature = "sqlite")]
+    use crate::services::migrator::MigratorService;
+    use crate::{
+        services::i18n::{I18nService, LocaleService},
+        validation::{validate, FieldErrors},
+    };
+
+    fn message(title: &str, content: &str) -> MessageModel {
+        MessageModel {
+            id: None,
+            channel_id: ChannelModel::GENERAL,
+            parent_id: None,
+            author: None,
+            title: title.to_owned(),
+            content: content.to_owned(),
+            replies: 0,
+            reactions: Vec::new(),
+            tags: Vec::new(),
+            mentions: Vec::new(),
+        }
+    }
+
+    fn locale() -> LocaleService {
+        LocaleService::new(Arc::new(I18nService::new().unwrap()), None, None)
+    }
+
+    fn errors(config: &Config, title: &str, content: &str) -> FieldErrors {
+        validate(&message(title, content), config, &locale())
+            .err()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn rejects_empty_title() {
+        let config = Config::test();
+        assert_eq!(
+            errors(&config, "", "a").get("title"),
+            Some("Title must be at least 1 character long.")
+        );
+    }
+
+    #[test]
+    fn rejects_whitespace_only_title() {
+        let config = Config::test();
+        assert_eq!(
+            errors(&config, " \t ", "a").get("title"),
+            Some("Title must not consist of whitespace only.")
+        );
+        assert!(errors(&config, "\u{3000}", "a").get("title").is_some());
+        assert!(errors(&config, " a ", "a").is_empty());
+    }
+
+    #[test]
+    fn limits_title_by_configuration() {
+        let mut config = Config::test();
+        config.messages_title_max = 3;
+        assert!(errors(&config, "abc", "a").is_empty());
+        assert_eq!(
+            errors(&config, "abcd", "a").get("title"),
+            Some("Title must not be more than 3 characters long.")
+        );
+    }
+
+    #[test]
+    fn counts_title_characters_instead_of_bytes() {
+        let mut config = Config::test();
+        config.messages_title_max = 3;
+        assert!(errors(&config, "ééé", "a").is_empty());
+        assert!(errors(&config, "日本語", "a").is_empty());
+        assert!(errors(&config, "日本語!", "a").get("title").is_some());
+    }
+
+    #[test]
+    fn rejects_empty_content() {
+        let config = Config::test();
+        assert_eq!(
+            errors(&config, "a", "").get("content"),
+            Some("Content must be at least 1 character long.")
+        );
+        assert!(errors(&config, "a", "a").is_empty());
+    }
+
+    #[test]
+    fn limits_content_by_configuration() {
+        let mut config = Config::test();
+        config.messages_content_max = 150;
+        assert!(errors(&config, "a", &"a".repeat(150)).is_empty());
+        assert_eq!(
+            errors(&config, "a", &"a".repeat(151)).get("content"),
+            Some("Content must not be more than 150 characters long.")
+        );
+    }
+
+    #[test]
+    fn counts_content_characters_instead_of_bytes() {
+        let mut config = Config::test();
+        config.messages_content_max = 2;
+        assert!(errors(&config, "a", "🦀🦀").is_empty());
+        assert!(errors(&config, "a", "🦀🦀🦀").get("content").is_some());
+    }
+
+    #[test]
+    fn default_limits_match_columns() {
+        let config = Config::test();
+        let title = "é".repeat(TITLE_LENGTH);
+        let content = "é".repeat(CONTENT_LENGTH);
+        assert!(errors(&config, &title, &content).is_empty());
+        let title = "é".repeat(TITLE_LENGTH + 1);
+        let content = "é".repeat(CONTENT_LENGTH + 1);
+        let errors = errors(&config, &title, &content);
+        assert!(errors.get("title").is_some());
+        assert!(errors.get("content").is_some());
+    }
+
+    #[test]
+    fn strips_control_characters() {
+        let message =
+            message("a\u{0}b\nc\td\u{1b}", "a\u{0}b\r\nc\td\u{7f}").sanitize();
+        assert_eq!(message.title, "abcd");
+        assert_eq!(message.content, "ab\nc\td");
+    }
+
+    #[test]
+    fn validates_after_stripping_control_characters() {
+        let config = Config::test();
+        let message = message("\u{0}\u{1}", "\u{0}").sanitize();
+        let errors = validate(&message, &config, &locale()).unwrap_err();
+        assert!(errors.get("title").is_some());
+        assert!(errors.get("content").is_some());
+    }
+
+    #[test]
+    fn normalizes_and_limits_tags() {
+        let config = Config::test();
+        let mut tagged = message("a", "a");
+        tagged.tags = vec!["#Rust".to_owned(), "rust".to_owned()];
+        let tagged = tagged.sanitize();
+        assert_eq!(tagged.tags, ["rust"]);
+        assert!(validate(&tagged, &config, &locale()).is_ok());
+        let mut tagged = message("a", "a");
+        tagged.tags = (0..=TagModel::TAGS_MAX).map(|i| i.to_string()).collect();
+        let errors =
+            validate(&tagged.sanitize(), &config, &locale()).unwrap_err();
+        assert!(errors.get("tags").is_some());
+    }
+
+    #[test]
+    fn accepts_valid_message() {
+        let config = Config::test();
+        assert!(validate(
+            &message("Hello", "Hello, world!"),
+            &config,
+            &locale()
+        )
+        .is_ok());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn filters_roots_by_all_tags(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let database = sqlx::pool::PoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await?;
+        MigratorService::migrate(&database, true).await?;
+        query("INSERT INTO users (name, password) VALUES ('bob', '');")
+            .execute(&database)
+            .await?;
+        for tags in [&["rust"][..], &["rust", "web"], &[]] {
+            let mut transaction = database.begin().await?;
+            let id = MessageModel::create(
+                &mut transaction,
+                ChannelModel::GENERAL,
+                None,
+                "bob",
+                "a",
+                "a",
+            )
+            .await?;
+            let tags: Vec<String> = tags.iter().map(|&t| t.into()).collect();
+            TagModel::set(&mut transaction, id, &tags).await?;
+            transaction.commit().await?;
         }
+        let roots = |tags: &[&str]| {
+            let tags: Vec<String> = tags.iter().map(|&t| t.into()).collect();
+            let database = database.clone();
+            async move {
+                MessageModel::roots(&database, ChannelModel::GENERAL, &tags)
+                    .await
+            }
+        };
+        assert_eq!(roots(&[]).await?.len(), 3);
+        let rust = roots(&["rust"]).await?;
+        assert_eq!(rust.len(), 2);
+        assert_eq!(rust[0].tags, ["rust", "web"]);
+        assert_eq!(rust[1].tags, ["rust"]);
+        let both = roots(&["rust", "web"]).await?;
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].id, rust[0].id);
+        assert!(roots(&["go"]).await?.is_empty());
+        Ok(())
     }
 }
@@ -1,2 +1,12 @@
+pub mod audit;
+pub mod channel;
+pub mod conversation;
+pub mod direct_message;
+pub mod mention;
 pub mod message;
+pub mod notification;
+pub mod pin;
+pub mod reaction;
+pub mod star;
+pub mod tag;
 pub mod user;
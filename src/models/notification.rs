@@ -0,0 +1,316 @@
+use serde::Serialize;
+use sqlx::{query, query_as, query_scalar, Error, FromRow};
+use time::{macros::format_description, OffsetDateTime};
+use tracing::instrument;
+
+use crate::database::{sql, DatabasePool, DatabaseQueryResult};
+
+#[derive(Clone, Debug, FromRow, Serialize)]
+pub struct NotificationModel {
+    pub id: i32,
+    pub user_name: String,
+    pub kind: String,
+    pub actor: Option<String>,
+    pub message_id: Option<i32>,
+    pub detail: Option<String>,
+    pub seen: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Clone, Debug, FromRow, PartialEq, Serialize)]
+pub struct NotificationPreferenceModel {
+    pub kind: String,
+    pub enabled: bool,
+}
+
+impl NotificationModel {
+    pub const MENTION: &'static str = "mention";
+    pub const REPLY: &'static str = "reply";
+    pub const EDIT: &'static str = "edit";
+    pub const ADMIN: &'static str = "admin";
+    pub const KINDS: [&'static str; 4] =
+        [Self::MENTION, Self::REPLY, Self::EDIT, Self::ADMIN];
+
+    #[instrument(level = "trace")]
+    pub async fn find(
+        database: &DatabasePool,
+        id: i32,
+        user: &str,
+    ) -> Result<Option<Self>, Error> {
+        query_as(&sql(concat!(
+            "SELECT * FROM notifications WHERE id = ? AND user_name = ? ",
+            "LIMIT 1;",
+        )))
+        .bind(id)
+        .bind(user)
+        .fetch_optional(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn page(
+        database: &DatabasePool,
+        user: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, Error> {
+        query_as(&sql(concat!(
+            "SELECT * FROM notifications WHERE user_name = ? ",
+            "ORDER BY id DESC LIMIT ? OFFSET ?;",
+        )))
+        .bind(user)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn count(
+        database: &DatabasePool,
+        user: &str,
+    ) -> Result<i64, Error> {
+        query_scalar(&sql(
+            "SELECT COUNT(*) FROM notifications WHERE user_name = ?;",
+        ))
+        .bind(user)
+        .fetch_one(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn unread(
+        database: &DatabasePool,
+        user: &str,
+    ) -> Result<i64, Error> {
+        query_scalar(&sql(concat!(
+            "SELECT COUNT(*) FROM notifications ",
+            "WHERE user_name = ? AND seen = FALSE;",
+        )))
+        .bind(user)
+        .fetch_one(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn create(
+        database: &DatabasePool,
+        user: &str,
+        kind: &str,
+        actor: Option<&str>,
+        detail: Option<&str>,
+        message_id: Option<i32>,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(concat!(
+            "INSERT INTO notifications ",
+            "(user_name, kind, actor, detail, message_id) ",
+            "VALUES (?, ?, ?, ?, ?);",
+        )))
+        .bind(user)
+        .bind(kind)
+        .bind(actor)
+        .bind(detail)
+        .bind(message_id)
+        .execute(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn send(
+        database: &DatabasePool,
+        user: &str,
+        kind: &str,
+        actor: Option<&str>,
+        detail: Option<&str>,
+        message_id: Option<i32>,
+    ) -> Result<bool, Error> {
+        if !Self::enabled(database, user, kind).await? {
+            return Ok(false);
+        }
+        Self::create(database, user, kind, actor, detail, message_id).await?;
+        Ok(true)
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn read(
+        database: &DatabasePool,
+        id: i32,
+        user: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(
+            "UPDATE notifications SET seen = TRUE WHERE id = ? AND user_name = ?;",
+        ))
+        .bind(id)
+        .bind(user)
+        .execute(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn read_all(
+        database: &DatabasePool,
+        user: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(
+            "UPDATE notifications SET seen = TRUE WHERE user_name = ?;",
+        ))
+        .bind(user)
+        .execute(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn enabled(
+        database: &DatabasePool,
+        user: &str,
+        kind: &str,
+    ) -> Result<bool, Error> {
+        let enabled: Option<bool> = query_scalar(&sql(concat!(
+            "SELECT enabled FROM notification_preferences ",
+            "WHERE user_name = ? AND kind = ? LIMIT 1;",
+        )))
+        .bind(user)
+        .bind(kind)
+        .fetch_optional(database)
+        .await?;
+        Ok(enabled.unwrap_or(true))
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn preferences(
+        database: &DatabasePool,
+        user: &str,
+    ) -> Result<Vec<NotificationPreferenceModel>, Error> {
+        let preferences = query_as(&sql(concat!(
+            "SELECT kind, enabled FROM notification_preferences ",
+            "WHERE user_name = ?;",
+        )))
+        .bind(user)
+        .fetch_all(database)
+        .await?;
+        Ok(Self::defaults(&preferences))
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn prefer(
+        database: &DatabasePool,
+        user: &str,
+        kind: &str,
+        enabled: bool,
+    ) -> Result<DatabaseQueryResult, Error> {
+        let mut transaction = database.begin().await?;
+        query(&sql(concat!(
+            "DELETE FROM notification_preferences ",
+            "WHERE user_name = ? AND kind = ?;",
+        )))
+        .bind(user)
+        .bind(kind)
+        .execute(&mut *transaction)
+        .await?;
+        let result = query(&sql(concat!(
+            "INSERT INTO notification_preferences (user_name, kind, enabled) ",
+            "VALUES (?, ?, ?);",
+        )))
+        .bind(user)
+        .bind(kind)
+        .bind(enabled)
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(result)
+    }
+
+    pub fn defaults(
+        preferences: &[NotificationPreferenceModel],
+    ) -> Vec<NotificationPreferenceModel> {
+        Self::KINDS
+            .iter()
+            .map(|kind| NotificationPreferenceModel {
+                kind: (*kind).to_owned(),
+                enabled: preferences
+                    .iter()
+                    .find(|preference| preference.kind == *kind)
+                    .is_none_or(|preference| preference.enabled),
+            })
+            .collect()
+    }
+
+    pub fn key(&self) -> String {
+        match &self.detail {
+            Some(detail) => format!("notification-{}-{detail}", self.kind),
+            None => format!("notification-{}", self.kind),
+        }
+    }
+
+    pub fn timestamp(&self) -> String {
+        self.created_at
+            .format(format_description!("[year]-[month]-[day] [hour]:[minute]"))
+            .unwrap_or_else(|_| self.created_at.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "sqlite")]
+    use crate::services::migrator::MigratorService;
+
+    #[test]
+    fn enables_kinds_by_default() {
+        let preferences = NotificationModel::defaults(&[
+            NotificationPreferenceModel {
+                kind: NotificationModel::REPLY.to_owned(),
+                enabled: false,
+            },
+            NotificationPreferenceModel {
+                kind: "unknown".to_owned(),
+                enabled: false,
+            },
+        ]);
+        assert_eq!(preferences.len(), NotificationModel::KINDS.len());
+        for preference in preferences {
+            assert_eq!(
+                preference.enabled,
+                preference.kind != NotificationModel::REPLY
+            );
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn sends_only_enabled_kinds() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let database = sqlx::pool::PoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await?;
+        MigratorService::migrate(&database, true).await?;
+        query("INSERT INTO users (name, password) VALUES ('bob', '');")
+            .execute(&database)
+            .await?;
+        let send = |kind| {
+            NotificationModel::send(&database, "bob", kind, None, None, None)
+        };
+        NotificationModel::prefer(
+            &database,
+            "bob",
+            NotificationModel::REPLY,
+            false,
+        )
+        .await?;
+        assert!(!send(NotificationModel::REPLY).await?);
+        assert!(send(NotificationModel::EDIT).await?);
+        assert_eq!(NotificationModel::count(&database, "bob").await?, 1);
+        NotificationModel::prefer(
+            &database,
+            "bob",
+            NotificationModel::REPLY,
+            true,
+        )
+        .await?;
+        assert!(send(NotificationModel::REPLY).await?);
+        assert_eq!(NotificationModel::count(&database, "bob").await?, 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use sqlx::{query, query_scalar, Error};
+use tracing::instrument;
+
+use crate::database::{sql, DatabasePool, DatabaseQueryResult};
+
+pub struct PinModel;
+
+impl PinModel {
+    #[instrument(level = "trace")]
+    pub async fn exists(
+        database: &DatabasePool,
+        message_id: i32,
+    ) -> Result<bool, Error> {
+        query_scalar::<_, i32>(&sql(
+            "SELECT message_id FROM pinned_messages WHERE message_id = ?;",
+        ))
+        .bind(message_id)
+        .fetch_optional(database)
+        .await
+        .map(|pin| pin.is_some())
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn create(
+        database: &DatabasePool,
+        message_id: i32,
+        user: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(
+            "INSERT INTO pinned_messages (message_id, pinned_by) VALUES (?, ?);",
+        ))
+        .bind(message_id)
+        .bind(user)
+        .execute(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn delete(
+        database: &DatabasePool,
+        message_id: i32,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql("DELETE FROM pinned_messages WHERE message_id = ?;"))
+            .bind(message_id)
+            .execute(database)
+            .await
+    }
+}
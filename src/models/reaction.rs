@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{query, query_as, query_scalar, Error, FromRow, QueryBuilder};
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::database::{sql, DatabasePool, DatabaseQueryResult};
+
+#[derive(Clone, Debug, FromRow)]
+pub struct ReactionModel {
+    pub message_id: i32,
+    pub user_name: String,
+    pub reaction: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct ReactionCountModel {
+    pub reaction: String,
+    pub count: i64,
+    pub users: Vec<String>,
+}
+
+impl ReactionModel {
+    pub const REACTIONS: [(&'static str, &'static str); 6] = [
+        ("like", "👍"),
+        ("love", "❤️"),
+        ("laugh", "😂"),
+        ("wow", "😮"),
+        ("sad", "😢"),
+        ("party", "🎉"),
+    ];
+
+    #[instrument(level = "trace")]
+    pub async fn find(
+        database: &DatabasePool,
+        message_id: i32,
+        user: &str,
+    ) -> Result<Option<String>, Error> {
+        query_scalar(&sql(concat!(
+            "SELECT reaction FROM message_reactions ",
+            "WHERE message_id = ? AND user_name = ? LIMIT 1;",
+        )))
+        .bind(message_id)
+        .bind(user)
+        .fetch_optional(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn message(
+        database: &DatabasePool,
+        message_id: i32,
+    ) -> Result<Vec<Self>, Error> {
+        query_as(&sql(concat!(
+            "SELECT * FROM message_reactions WHERE message_id = ? ",
+            "ORDER BY user_name;",
+        )))
+        .bind(message_id)
+        .fetch_all(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn messages(
+        database: &DatabasePool,
+        message_ids: &[i32],
+    ) -> Result<Vec<Self>, Error> {
+        if message_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut query = QueryBuilder::new(
+            "SELECT * FROM message_reactions WHERE message_id IN (",
+        );
+        let mut ids = query.separated(", ");
+        for id in message_ids {
+            ids.push_bind(*id);
+        }
+        ids.push_unseparated(") ORDER BY user_name;");
+        query.build_query_as().fetch_all(database).await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn set(
+        database: &DatabasePool,
+        message_id: i32,
+        user: &str,
+        reaction: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        let mut transaction = database.begin().await?;
+        query(&sql(
+            "DELETE FROM message_reactions WHERE message_id = ? AND user_name = ?;",
+        ))
+        .bind(message_id)
+        .bind(user)
+        .execute(&mut *transaction)
+        .await?;
+        let result = query(&sql(concat!(
+            "INSERT INTO message_reactions (message_id, user_name, reaction) ",
+            "VALUES (?, ?, ?);",
+        )))
+        .bind(message_id)
+        .bind(user)
+        .bind(reaction)
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(result)
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn delete(
+        database: &DatabasePool,
+        message_id: i32,
+        user: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(
+            "DELETE FROM message_reactions WHERE message_id = ? AND user_name = ?;",
+        ))
+        .bind(message_id)
+        .bind(user)
+        .execute(database)
+        .await
+    }
+
+    pub fn valid(reaction: &str) -> bool {
+        Self::REACTIONS.iter().any(|(name, _)| *name == reaction)
+    }
+
+    pub fn counts(
+        reactions: &[Self],
+        message_id: i32,
+    ) -> Vec<ReactionCountModel> {
+        Self::REACTIONS
+            .iter()
+            .filter_map(|(name, _)| {
+                let users: Vec<String> = reactions
+                    .iter()
+                    .filter(|reaction| {
+                        reaction.message_id == message_id
+                            && reaction.reaction == *name
+                    })
+                    .map(|reaction| reaction.user_name.clone())
+                    .collect();
+                (!users.is_empty()).then(|| ReactionCountModel {
+                    reaction: (*name).to_owned(),
+                    count: i64::try_from(users.len()).unwrap_or(i64::MAX),
+                    users,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reaction(message_id: i32, user: &str, reaction: &str) -> ReactionModel {
+        ReactionModel {
+            message_id,
+            user_name: user.to_owned(),
+            reaction: reaction.to_owned(),
+        }
+    }
+
+    #[test]
+    fn counts_reactions_of_message() {
+        let reactions = [
+            reaction(1, "alice", "party"),
+            reaction(1, "bob", "like"),
+            reaction(1, "carol", "like"),
+            reaction(2, "dave", "like"),
+        ];
+        let counts = ReactionModel::counts(&reactions, 1);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0].reaction, "like");
+        assert_eq!(counts[0].count, 2);
+        assert_eq!(counts[0].users, ["bob", "carol"]);
+        assert_eq!(counts[1].reaction, "party");
+        assert_eq!(counts[1].users, ["alice"]);
+        assert!(ReactionModel::counts(&reactions, 3).is_empty());
+    }
+
+    #[test]
+    fn validates_reactions() {
+        assert!(ReactionModel::valid("like"));
+        assert!(!ReactionModel::valid("👍"));
+    }
+}
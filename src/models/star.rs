@@ -0,0 +1,55 @@
+use sqlx::{query, query_scalar, Error};
+use tracing::instrument;
+
+use crate::database::{sql, DatabasePool, DatabaseQueryResult};
+
+pub struct StarModel;
+
+impl StarModel {
+    #[instrument(level = "trace")]
+    pub async fn exists(
+        database: &DatabasePool,
+        message_id: i32,
+        user: &str,
+    ) -> Result<bool, Error> {
+        query_scalar::<_, i32>(&sql(concat!(
+            "SELECT message_id FROM starred_messages ",
+            "WHERE user_name = ? AND message_id = ?;",
+        )))
+        .bind(user)
+        .bind(message_id)
+        .fetch_optional(database)
+        .await
+        .map(|star| star.is_some())
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn create(
+        database: &DatabasePool,
+        message_id: i32,
+        user: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(
+            "INSERT INTO starred_messages (user_name, message_id) VALUES (?, ?);",
+        ))
+        .bind(user)
+        .bind(message_id)
+        .execute(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn delete(
+        database: &DatabasePool,
+        message_id: i32,
+        user: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql(
+            "DELETE FROM starred_messages WHERE user_name = ? AND message_id = ?;",
+        ))
+        .bind(user)
+        .bind(message_id)
+        .execute(database)
+        .await
+    }
+}
@@ -0,0 +1,286 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use sqlx::{
+    query, query_as, query_scalar, Error, FromRow, QueryBuilder, Transaction,
+};
+use tracing::instrument;
+use utoipa::ToSchema;
+use validator::ValidationError;
+
+use crate::database::{sql, Database, DatabasePool};
+
+pub const NAME_LENGTH: usize = 30;
+
+#[cfg(feature = "mysql")]
+const INSERT: &str = "INSERT IGNORE INTO tags (name) VALUES (?);";
+
+#[cfg(not(feature = "mysql"))]
+const INSERT: &str =
+    "INSERT INTO tags (name) VALUES (?) ON CONFLICT (name) DO NOTHING;";
+
+#[derive(Clone, Debug, FromRow)]
+pub struct TagModel {
+    pub message_id: i32,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize, ToSchema)]
+pub struct TagCountModel {
+    pub name: String,
+    pub count: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Tags {
+    List(Vec<String>),
+    Text(String),
+}
+
+impl TagModel {
+    pub const TAGS_MAX: usize = 10;
+    pub const SUGGESTIONS_MAX: usize = 10;
+
+    #[instrument(level = "trace")]
+    pub async fn message(
+        database: &DatabasePool,
+        message_id: i32,
+    ) -> Result<Vec<String>, Error> {
+        query_scalar(&sql(concat!(
+            "SELECT tags.name FROM tags ",
+            "JOIN message_tags ON message_tags.tag_id = tags.id ",
+            "WHERE message_tags.message_id = ? ORDER BY tags.name;",
+        )))
+        .bind(message_id)
+        .fetch_all(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn messages(
+        database: &DatabasePool,
+        message_ids: &[i32],
+    ) -> Result<Vec<Self>, Error> {
+        if message_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut query = QueryBuilder::new(concat!(
+            "SELECT message_tags.message_id, tags.name FROM tags ",
+            "JOIN message_tags ON message_tags.tag_id = tags.id ",
+            "WHERE message_tags.message_id IN (",
+        ));
+        let mut ids = query.separated(", ");
+        for id in message_ids {
+            ids.push_bind(*id);
+        }
+        ids.push_unseparated(") ORDER BY tags.name;");
+        query.build_query_as().fetch_all(database).await
+    }
+
+    pub fn filter<'a>(
+        query: &mut QueryBuilder<'a, Database>,
+        names: &'a [String],
+    ) {
+        if names.is_empty() {
+            return;
+        }
+        query.push(concat!(
+            " AND id IN (SELECT message_tags.message_id FROM message_tags ",
+            "JOIN tags ON tags.id = message_tags.tag_id WHERE tags.name IN (",
+        ));
+        let mut tags = query.separated(", ");
+        for name in names {
+            tags.push_bind(name.as_str());
+        }
+        tags.push_unseparated(
+            ") GROUP BY message_tags.message_id HAVING COUNT(*) = ",
+        );
+        query.push_bind(i64::try_from(names.len()).unwrap_or(i64::MAX));
+        query.push(")");
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn counts(
+        database: &DatabasePool,
+        channel_id: i32,
+    ) -> Result<Vec<TagCountModel>, Error> {
+        query_as(&sql(concat!(
+            "SELECT tags.name, COUNT(*) AS count FROM tags ",
+            "JOIN message_tags ON message_tags.tag_id = tags.id ",
+            "JOIN messages ON messages.id = message_tags.message_id ",
+            "WHERE messages.channel_id = ? AND messages.parent_id IS NULL ",
+            "GROUP BY tags.name ORDER BY tags.name;",
+        )))
+        .bind(channel_id)
+        .fetch_all(database)
+        .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn visible(
+        database: &DatabasePool,
+        user: &str,
+    ) -> Result<Vec<String>, Error> {
+        query_scalar(&sql(concat!(
+            "SELECT DISTINCT tags.name FROM tags ",
+            "JOIN message_tags ON message_tags.tag_id = tags.id ",
+            "JOIN messages ON messages.id = message_tags.message_id ",
+            "JOIN channels ON channels.id = messages.channel_id ",
+            "WHERE channels.private = FALSE OR channels.id IN (",
+            "SELECT channel_id FROM channel_members WHERE user_name = ?",
+            ") ORDER BY tags.name;",
+        )))
+        .bind(user)
+        .fetch_all(database)
+        .await
+    }
+
+    #[instrument(level = "trace", skip(transaction))]
+    pub async fn set(
+        transaction: &mut Transaction<'_, Database>,
+        message_id: i32,
+        names: &[String],
+    ) -> Result<(), Error> {
+        query(&sql("DELETE FROM message_tags WHERE message_id = ?;"))
+            .bind(message_id)
+            .execute(&mut **transaction)
+            .await?;
+        for name in names {
+            query(&sql(INSERT))
+                .bind(name)
+                .execute(&mut **transaction)
+                .await?;
+            query(&sql(concat!(
+                "INSERT INTO message_tags (message_id, tag_id) ",
+                "VALUES (?, (SELECT id FROM tags WHERE name = ?));",
+            )))
+            .bind(message_id)
+            .bind(name)
+            .execute(&mut **transaction)
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub fn names(tags: &[Self], message_id: i32) -> Vec<String> {
+        tags.iter()
+            .filter(|tag| tag.message_id == message_id)
+            .map(|tag| tag.name.clone())
+            .collect()
+    }
+
+    pub fn parse(input: &str) -> Vec<String> {
+        Self::normalize(Self::split(input))
+    }
+
+    pub fn normalize(tags: Vec<String>) -> Vec<String> {
+        let mut tags: Vec<String> = tags
+            .iter()
+            .map(|tag| {
+                tag.chars()
+                    .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_'))
+                    .flat_map(char::to_lowercase)
+                    .collect::<String>()
+            })
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    pub fn suggest(input: &str, names: &[String]) -> Vec<String> {
+        let split = input
+            .char_indices()
+            .rev()
+            .find(|(_, c)| Self::separator(*c))
+            .map_or(0, |(index, c)| index + c.len_utf8());
+        let (head, last) = input.split_at(split);
+        let Some(prefix) = Self::parse(last).pop() else {
+            return Vec::new();
+        };
+        let current = Self::parse(head);
+        names
+            .iter()
+            .filter(|name| name.starts_with(&prefix) && **name != prefix)
+            .filter(|name| !current.contains(name))
+            .take(Self::SUGGESTIONS_MAX)
+            .map(|name| format!("{head}{name}"))
+            .collect()
+    }
+
+    fn split(input: &str) -> Vec<String> {
+        input
+            .split(Self::separator)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn separator(c: char) -> bool {
+        c == ',' || c.is_whitespace()
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Tags::deserialize(deserializer)? {
+        Tags::List(tags) => tags,
+        Tags::Text(text) => TagModel::split(&text),
+    })
+}
+
+pub fn validate(tags: &[String]) -> Result<(), ValidationError> {
+    if TagModel::TAGS_MAX < tags.len()
+        || tags.iter().any(|tag| NAME_LENGTH < tag.chars().count())
+    {
+        let mut error = ValidationError::new("tags");
+        error.add_param("max".into(), &TagModel::TAGS_MAX);
+        error.add_param("length".into(), &NAME_LENGTH);
+        return Err(error);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| (*name).to_owned()).collect()
+    }
+
+    #[test]
+    fn parses_tags() {
+        assert_eq!(
+            TagModel::parse("Rust, #web  rust,,Ünnep\tc++"),
+            ["c", "rust", "web", "ünnep"]
+        );
+        assert!(TagModel::parse(" , # ").is_empty());
+    }
+
+    #[test]
+    fn suggests_completions_of_last_tag() {
+        let tags = names(&["rust", "rustweb", "web", "webdev"]);
+        assert_eq!(
+            TagModel::suggest("rust, we", &tags),
+            ["rust, web", "rust, webdev"]
+        );
+        assert_eq!(
+            TagModel::suggest("web ru", &tags),
+            ["web rust", "web rustweb"]
+        );
+        assert_eq!(TagModel::suggest("rust", &tags), ["rustweb"]);
+        assert!(TagModel::suggest("rust, ", &tags).is_empty());
+        assert!(TagModel::suggest("rustweb, rust", &tags).is_empty());
+    }
+
+    #[test]
+    fn validates_tags() {
+        assert!(validate(&names(&["a"; TagModel::TAGS_MAX])).is_ok());
+        assert!(validate(&names(&["a"; TagModel::TAGS_MAX + 1])).is_err());
+        assert!(validate(&["é".repeat(NAME_LENGTH)]).is_ok());
+        assert!(validate(&["é".repeat(NAME_LENGTH + 1)]).is_err());
+    }
+}
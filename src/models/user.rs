@@ -4,23 +4,37 @@ use std::{
 };
 
 use axum_login::AuthUser;
-use password_auth::generate_hash;
+use password_auth::{generate_hash, verify_password};
 use serde::{Deserialize, Serialize};
-use sqlx::{
-    mysql::MySqlQueryResult, query, query_as, Error, FromRow, MySqlPool,
-};
+use sqlx::{query, query_as, query_scalar, Error, FromRow};
 use tracing::{error, instrument};
+use validator::{Validate, ValidationError};
+
+use crate::{
+    config::Config,
+    database::{sql, DatabasePool, DatabaseQueryResult},
+    services::i18n::LocaleService,
+    validation::{max_length, min_length, not_empty, validate, FieldErrors},
+};
+
+pub const NAME_LENGTH: usize = 50;
 
-#[derive(Clone, Deserialize, FromRow, Serialize)]
+#[derive(Clone, Deserialize, FromRow, Serialize, Validate)]
+#[validate(context = Config)]
 pub struct UserModel {
+    #[validate(
+        custom(function = not_empty),
+        custom(function = name_max, use_context)
+    )]
     pub name: String,
+    #[validate(custom(function = password_min, use_context))]
     pub password: String,
-}
-
-#[derive(Serialize)]
-pub struct UserModelError {
-    pub name: Option<&'static str>,
-    pub password: Option<&'static str>,
+    #[serde(default)]
+    pub role: String,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 impl Debug for UserModel {
@@ -29,6 +43,9 @@ impl Debug for UserModel {
             .debug_struct("UserModel")
             .field("name", &self.name)
             .field("password", &"********")
+            .field("role", &self.role)
+            .field("disabled", &self.disabled)
+            .field("locale", &self.locale)
             .finish()
     }
 }
@@ -46,79 +63,163 @@ impl AuthUser for UserModel {
 }
 
 impl UserModel {
+    pub const ROLES: [&'static str; 3] = ["user", "moderator", "admin"];
+
+    pub fn is_admin(&self) -> bool {
+        self.role == "admin"
+    }
+
+    pub fn is_moderator(&self) -> bool {
+        self.role == "moderator" || self.is_admin()
+    }
+
     #[instrument(level = "trace")]
     pub async fn find(
-        database: &MySqlPool,
+        database: &DatabasePool,
         name: &str,
     ) -> Result<Option<Self>, Error> {
-        query_as!(Self, "SELECT * FROM users WHERE name = ? LIMIT 1;", name)
+        query_as(&sql("SELECT * FROM users WHERE name = ? LIMIT 1;"))
+            .bind(name)
             .fetch_optional(database)
             .await
     }
 
+    #[instrument(level = "trace", skip(token))]
+    pub async fn find_by_api_token(
+        database: &DatabasePool,
+        token: &str,
+    ) -> Result<Option<Self>, Error> {
+        let Some((name, secret)) = token.rsplit_once(':') else {
+            return Ok(None);
+        };
+        let hash: Option<Option<String>> = query_scalar(&sql(
+            "SELECT api_token FROM users WHERE name = ? LIMIT 1;",
+        ))
+        .bind(name)
+        .fetch_optional(database)
+        .await?;
+        match hash.flatten() {
+            Some(hash) if verify_password(secret, &hash).is_ok() => {
+                Self::find(database, name).await
+            }
+            _ => Ok(None),
+        }
+    }
+
     #[instrument(level = "trace")]
     pub async fn create(
-        database: &MySqlPool,
+        database: &DatabasePool,
         name: &str,
         password: &str,
-    ) -> Result<MySqlQueryResult, Error> {
-        query!(
-            "INSERT INTO users VALUES (?, ?);",
-            name,
-            generate_hash(password)
-        )
-        .execute(database)
-        .await
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql("INSERT INTO users (name, password) VALUES (?, ?);"))
+            .bind(name)
+            .bind(generate_hash(password))
+            .execute(database)
+            .await
     }
 
     #[instrument(level = "trace")]
-    pub async fn validate_name(
-        database: &MySqlPool,
+    pub async fn set_password(
+        database: &DatabasePool,
         name: &str,
-    ) -> Option<&'static str> {
-        if name.is_empty() {
-            return Some("Name must be at least 1 character long.");
-        }
-        if 100 < name.len() {
-            return Some("Name must not be more than 50 characters long.");
-        }
-        match query_as!(
-            Self,
-            "SELECT * FROM users WHERE name = ? LIMIT 1;",
-            name
-        )
-        .fetch_optional(database)
-        .await
-        {
-            Ok(Some(..)) => Some("Name already taken."),
-            Ok(None) => None,
-            Err(error) => {
-                error!("{error}");
-                Some("Internal server error.")
-            }
-        }
+        password: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql("UPDATE users SET password = ? WHERE name = ?;"))
+            .bind(generate_hash(password))
+            .bind(name)
+            .execute(database)
+            .await
     }
 
     #[instrument(level = "trace")]
-    pub fn validate_password(password: &str) -> Option<&'static str> {
-        if password.len() < 8 {
-            Some("Password must be at least 8 characters long.")
-        } else {
-            None
-        }
+    pub async fn set_role(
+        database: &DatabasePool,
+        name: &str,
+        role: &str,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql("UPDATE users SET role = ? WHERE name = ?;"))
+            .bind(role)
+            .bind(name)
+            .execute(database)
+            .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn set_disabled(
+        database: &DatabasePool,
+        name: &str,
+        disabled: bool,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql("UPDATE users SET disabled = ? WHERE name = ?;"))
+            .bind(disabled)
+            .bind(name)
+            .execute(database)
+            .await
+    }
+
+    #[instrument(level = "trace")]
+    pub async fn set_locale(
+        database: &DatabasePool,
+        name: &str,
+        locale: Option<&str>,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql("UPDATE users SET locale = ? WHERE name = ?;"))
+            .bind(locale)
+            .bind(name)
+            .execute(database)
+            .await
+    }
+
+    #[instrument(level = "trace", skip(token))]
+    pub async fn set_api_token(
+        database: &DatabasePool,
+        name: &str,
+        token: Option<&str>,
+    ) -> Result<DatabaseQueryResult, Error> {
+        query(&sql("UPDATE users SET api_token = ? WHERE name = ?;"))
+            .bind(token.map(generate_hash))
+            .bind(name)
+            .execute(database)
+            .await
     }
 
     #[instrument(level = "trace")]
     pub async fn validate(
-        database: &MySqlPool,
+        database: &DatabasePool,
+        config: &Config,
+        locale: &LocaleService,
         user: &Self,
-    ) -> Option<UserModelError> {
-        let name = Self::validate_name(database, &user.name).await;
-        let password = Self::validate_password(&user.password);
-        if name.is_some() || password.is_some() {
-            Some(UserModelError { name, password })
+    ) -> Result<(), FieldErrors> {
+        let mut errors =
+            validate(user, config, locale).err().unwrap_or_default();
+        if errors.get("name").is_none() {
+            match Self::find(database, &user.name).await {
+                Ok(Some(..)) => {
+                    errors.insert("name", locale.get("validation-taken"));
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    error!("{error}");
+                    errors.insert("name", locale.get("validation-internal"));
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
         } else {
-            None
+            Err(errors)
         }
     }
 }
+
+fn name_max(name: &str, config: &Config) -> Result<(), ValidationError> {
+    max_length(name, config.users_name_max)
+}
+
+fn password_min(
+    password: &str,
+    config: &Config,
+) -> Result<(), ValidationError> {
+    min_length(password, config.users_password_min)
+}
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use axum::{routing::get, Router};
+use tracing::instrument;
+
+use crate::{
+    controllers::api::{
+        channel,
+        docs::{docs, openapi},
+        message::{create, destroy, index, show, update},
+    },
+    services::state::StateService,
+};
+
+#[instrument(level = "debug")]
+pub fn routes() -> Router<Arc<StateService>> {
+    Router::new()
+        .route("/api/openapi.json", get(openapi))
+        .route("/api/docs", get(docs))
+        .route("/api/v1/channels", get(channel::index))
+        .route("/api/v1/messages", get(index).post(create))
+        .route(
+            "/api/v1/messages/{id}",
+            get(show).put(update).delete(destroy),
+        )
+}
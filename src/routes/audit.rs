@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use axum::{routing::get, Router};
+use axum_login::permission_required;
+use tracing::instrument;
+
+use crate::{
+    controllers::audit::{events, export, index},
+    services::{authenticator::AuthenticatorService, state::StateService},
+};
+
+#[instrument(level = "debug")]
+pub fn routes() -> Router<Arc<StateService>> {
+    Router::new()
+        .route("/audit", get(index))
+        .route("/audit/events", get(events))
+        .route("/audit/export", get(export))
+        .route_layer(permission_required!(AuthenticatorService, "admin"))
+}
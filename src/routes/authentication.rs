@@ -8,8 +8,7 @@ use tracing::instrument;
 
 use crate::{
     controllers::authentication::{
-        authentication, login, logout, register, validate_name,
-        validate_password,
+        authentication, login, logout, register, validate_field,
     },
     services::state::StateService,
 };
@@ -21,6 +20,5 @@ pub fn routes() -> Router<Arc<StateService>> {
         .route("/register", post(register))
         .route("/login", post(login))
         .route("/logout", post(logout))
-        .route("/validate/name", post(validate_name))
-        .route("/validate/password", post(validate_password))
+        .route("/validate/{field}", post(validate_field))
 }
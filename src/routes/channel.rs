@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use axum::{routing::post, Router};
+use tracing::instrument;
+
+use crate::{
+    controllers::channel::{add_member, create},
+    services::state::StateService,
+};
+
+#[instrument(level = "debug")]
+pub fn routes() -> Router<Arc<StateService>> {
+    Router::new()
+        .route("/channels", post(create))
+        .route("/channel/{id}/members", post(add_member))
+}
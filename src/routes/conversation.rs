@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tracing::instrument;
+
+use crate::{
+    controllers::conversation::{create, index, read, send, show, unread},
+    services::state::StateService,
+};
+
+#[instrument(level = "debug")]
+pub fn routes() -> Router<Arc<StateService>> {
+    Router::new()
+        .route("/conversations", get(index).post(create))
+        .route("/conversations/unread", get(unread))
+        .route("/conversation/{id}", get(show))
+        .route("/conversation/{id}/messages", post(send))
+        .route("/conversation/{id}/read", post(read))
+}
@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+use axum::{routing::get, Router};
+use tracing::instrument;
+
+use crate::{controllers::event::events, services::state::StateService};
+
+#[instrument(level = "debug")]
+pub fn routes() -> Router<Arc<StateService>> {
+    Router::new().route("/events", get(events))
+}
@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use axum::{routing::get, Router};
+use tracing::instrument;
+
+use crate::{
+    controllers::health::{healthz, readyz},
+    services::state::StateService,
+};
+
+#[instrument(level = "debug")]
+pub fn routes() -> Router<Arc<StateService>> {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/livez", get(healthz))
+        .route("/readyz", get(readyz))
+}
@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+use axum::{routing::post, Router};
+use tracing::instrument;
+
+use crate::{controllers::locale::update, services::state::StateService};
+
+#[instrument(level = "debug")]
+pub fn routes() -> Router<Arc<StateService>> {
+    Router::new().route("/locale", post(update))
+}
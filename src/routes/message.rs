@@ -4,14 +4,16 @@ use axum::{
     routing::{get, post},
     Router,
 };
+use axum_login::permission_required;
 use tracing::instrument;
 
 use crate::{
     controllers::message::{
-        create, destroy, events, index, show, update, validate_content,
-        validate_title,
+        create, destroy, events, index, pin, preview, react, reply,
+        reply_preview, reply_validate_field, show, star, update,
+        validate_field,
     },
-    services::state::StateService,
+    services::{authenticator::AuthenticatorService, state::StateService},
 };
 
 #[instrument(level = "debug")]
@@ -20,6 +22,22 @@ pub fn routes() -> Router<Arc<StateService>> {
         .route("/messages", get(index).post(create))
         .route("/message/{id}", get(show).put(update).delete(destroy))
         .route("/messages/events", get(events))
-        .route("/message/{id}/validate/title", post(validate_title))
-        .route("/message/{id}/validate/content", post(validate_content))
+        .route("/message/{id}/validate/{field}", post(validate_field))
+        .route("/message/{id}/preview", post(preview))
+        .route("/message/{id}/reactions", post(react))
+        .route("/message/{id}/star", post(star))
+        .route("/message/{id}/reply", post(reply))
+        .route(
+            "/message/{id}/reply/validate/{field}",
+            post(reply_validate_field),
+        )
+        .route("/message/{id}/reply/preview", post(reply_preview))
+        .merge(
+            Router::new()
+                .route("/message/{id}/pin", post(pin))
+                .route_layer(permission_required!(
+                    AuthenticatorService,
+                    "moderator"
+                )),
+        )
 }
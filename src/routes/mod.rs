@@ -1,6 +1,16 @@
+mod api;
+mod audit;
 mod authentication;
+mod channel;
+mod conversation;
 mod dashboard;
+mod event;
+pub mod health;
+mod locale;
 mod message;
+pub mod metrics;
+mod notification;
+mod tag;
 
 use std::sync::Arc;
 
@@ -8,14 +18,24 @@ use axum::Router;
 use axum_login::login_required;
 use tracing::instrument;
 
-use crate::services::{
-    authenticator::AuthenticatorService, state::StateService,
+use crate::{
+    error::AppError,
+    services::{authenticator::AuthenticatorService, state::StateService},
 };
 
 #[instrument(level = "debug")]
 pub fn routes() -> Router<Arc<StateService>> {
     message::routes()
         .merge(dashboard::routes())
+        .merge(channel::routes())
+        .merge(tag::routes())
+        .merge(conversation::routes())
+        .merge(notification::routes())
+        .merge(event::routes())
+        .merge(audit::routes())
+        .merge(locale::routes())
         .route_layer(login_required!(AuthenticatorService, login_url = "/"))
         .merge(authentication::routes())
+        .merge(api::routes())
+        .fallback(|| async { AppError::NotFound })
 }
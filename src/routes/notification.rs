@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use tracing::instrument;
+
+use crate::{
+    controllers::notification::{
+        index, list, menu, open, preferences, read, read_all, unread,
+    },
+    services::state::StateService,
+};
+
+#[instrument(level = "debug")]
+pub fn routes() -> Router<Arc<StateService>> {
+    Router::new()
+        .route("/notifications", get(index))
+        .route("/notifications/list", get(list))
+        .route("/notifications/menu", get(menu))
+        .route("/notifications/unread", get(unread))
+        .route("/notifications/read", post(read_all))
+        .route("/notifications/preferences", post(preferences))
+        .route("/notification/{id}/open", post(open))
+        .route("/notification/{id}/read", post(read))
+}
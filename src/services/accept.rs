@@ -0,0 +1,34 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header::ACCEPT, request::Parts},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct AcceptService {
+    json: bool,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AcceptService {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            json: parts
+                .headers
+                .get(ACCEPT)
+                .and_then(|accept| accept.to_str().ok())
+                .is_some_and(|accept| accept.contains("application/json")),
+        })
+    }
+}
+
+impl AcceptService {
+    pub fn json(&self) -> bool {
+        self.json
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts},
+};
+use axum_login::AuthSession;
+use tracing::instrument;
+
+use crate::{error::AppError, models::user::UserModel};
+
+use super::{authenticator::AuthenticatorService, state::StateService};
+
+#[derive(Debug, Clone)]
+pub struct ApiService {
+    user: UserModel,
+}
+
+impl FromRequestParts<Arc<StateService>> for ApiService {
+    type Rejection = AppError;
+
+    #[instrument(level = "trace", skip(parts))]
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<StateService>,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(token) = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|authorization| authorization.to_str().ok())
+            .and_then(|authorization| authorization.strip_prefix("Bearer "))
+        else {
+            return parts
+                .extensions
+                .get::<AuthSession<AuthenticatorService>>()
+                .and_then(|authenticator| authenticator.user.clone())
+                .map(|user| Self { user })
+                .ok_or(AppError::Unauthorized);
+        };
+        match UserModel::find_by_api_token(state.database(), token).await? {
+            Some(user) if !user.disabled => Ok(Self { user }),
+            _ => Err(AppError::Unauthorized),
+        }
+    }
+}
+
+impl ApiService {
+    pub fn user(&self) -> &UserModel {
+        &self.user
+    }
+}
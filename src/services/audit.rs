@@ -0,0 +1,68 @@
+use std::{convert::Infallible, net::SocketAddr};
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header::USER_AGENT, request::Parts},
+};
+use metrics::counter;
+use tracing::{error, instrument};
+
+use crate::{database::DatabasePool, models::audit::AuditEventModel};
+
+#[derive(Debug, Clone)]
+pub struct AuditService {
+    ip: Option<String>,
+    user_agent: Option<String>,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuditService {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            ip: parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(address)| address.ip().to_string()),
+            user_agent: parts
+                .headers
+                .get(USER_AGENT)
+                .and_then(|user_agent| user_agent.to_str().ok())
+                .map(truncate),
+        })
+    }
+}
+
+impl AuditService {
+    #[instrument(level = "trace")]
+    pub async fn record(
+        &self,
+        database: &DatabasePool,
+        actor: Option<&str>,
+        action: &str,
+        target: Option<&str>,
+    ) {
+        let actor = actor.map(truncate);
+        let target = target.map(truncate);
+        if let Err(error) = AuditEventModel::create(
+            database,
+            actor.as_deref(),
+            action,
+            target.as_deref(),
+            self.ip.as_deref(),
+            self.user_agent.as_deref(),
+        )
+        .await
+        {
+            error!("failed to record audit event {action}: {error}");
+            counter!("audit_failures_total").increment(1);
+        }
+    }
+}
+
+fn truncate(value: &str) -> String {
+    value.chars().take(255).collect()
+}
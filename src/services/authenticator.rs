@@ -1,17 +1,19 @@
+use std::collections::HashSet;
+
 use async_trait::async_trait;
 use axum_login::{
     tower_sessions::{Expiry, MemoryStore, SessionManagerLayer},
-    AuthManagerLayer, AuthManagerLayerBuilder, AuthnBackend, UserId,
+    AuthManagerLayer, AuthManagerLayerBuilder, AuthnBackend, AuthzBackend,
+    UserId,
 };
 use password_auth::verify_password;
-use sqlx::{Error, MySqlPool};
-use time::Duration;
+use sqlx::Error;
 use tracing::instrument;
 
-use crate::models::user::UserModel;
+use crate::{config::Config, database::DatabasePool, models::user::UserModel};
 
 #[derive(Debug, Clone)]
-pub struct AuthenticatorService(MySqlPool);
+pub struct AuthenticatorService(DatabasePool);
 
 #[async_trait]
 impl AuthnBackend for AuthenticatorService {
@@ -34,21 +36,41 @@ impl AuthnBackend for AuthenticatorService {
         &self,
         name: &UserId<Self>,
     ) -> Result<Option<Self::User>, Self::Error> {
-        UserModel::find(&self.0, name).await
+        Ok(UserModel::find(&self.0, name)
+            .await?
+            .filter(|user| !user.disabled))
+    }
+}
+
+#[async_trait]
+impl AuthzBackend for AuthenticatorService {
+    type Permission = String;
+
+    #[instrument(level = "trace")]
+    async fn get_user_permissions(
+        &self,
+        user: &Self::User,
+    ) -> Result<HashSet<Self::Permission>, Self::Error> {
+        let mut permissions = HashSet::from([user.role.clone()]);
+        if user.is_moderator() {
+            permissions.insert("moderator".to_owned());
+        }
+        Ok(permissions)
     }
 }
 
 impl AuthenticatorService {
     #[instrument(level = "debug")]
     pub async fn new(
-        database: MySqlPool,
+        database: DatabasePool,
+        config: &Config,
     ) -> Result<AuthManagerLayer<AuthenticatorService, MemoryStore>, Error>
     {
         Ok(AuthManagerLayerBuilder::new(
             AuthenticatorService(database),
             SessionManagerLayer::new(MemoryStore::default())
-                .with_expiry(Expiry::OnInactivity(Duration::days(1)))
-                .with_secure(false),
+                .with_expiry(Expiry::OnInactivity(config.session_expiry))
+                .with_secure(config.session_secure),
         )
         .build())
     }
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    body::Body,
+    extract::Request,
+    middleware::Next,
+    response::{Html, IntoResponse, Response},
+    Json,
+};
+use axum_login::AuthSession;
+use serde::Serialize;
+use tracing::{error, instrument};
+use utoipa::ToSchema;
+
+use crate::{
+    error::AppErrorMessage,
+    templates::{error::ErrorTemplate, toast::ToastTemplate},
+};
+
+use super::{
+    accept::AcceptService, authenticator::AuthenticatorService,
+    i18n::LocaleService,
+};
+
+#[derive(Serialize, ToSchema)]
+pub struct ErrorBody {
+    error: String,
+}
+
+#[instrument(level = "trace", skip(authenticator))]
+pub async fn error_service(
+    authenticator: AuthSession<AuthenticatorService>,
+    accept: AcceptService,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let json = accept.json() || request.uri().path().starts_with("/api/");
+    let htmx = request.headers().contains_key("HX-Request");
+    let token = request.extensions().get::<Arc<String>>().cloned();
+    let locale = request.extensions().get::<LocaleService>().cloned();
+    let response = next.run(request).await;
+    let (Some(AppErrorMessage(key)), Some(locale)) =
+        (response.extensions().get().copied(), locale)
+    else {
+        return response;
+    };
+    let message = locale.get(key);
+    let (parts, _) = response.into_parts();
+    let status = parts.status;
+    if json {
+        return (parts, Json(ErrorBody { error: message })).into_response();
+    }
+    let rendered = if htmx {
+        ToastTemplate::new(&message)
+            .render()
+            .map(|toast| ([("HX-Reswap", "none")], Html(toast)).into_response())
+    } else {
+        ErrorTemplate::new(
+            token.as_deref().map_or("", String::as_str),
+            &locale,
+            authenticator.user.as_ref(),
+            status.as_u16(),
+            status.canonical_reason().unwrap_or_default(),
+            &message,
+        )
+        .render()
+        .map(|page| Html(page).into_response())
+    };
+    match rendered {
+        Ok(rendered) => (parts, rendered).into_response(),
+        Err(error) => {
+            error!("{error}");
+            status.into_response()
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::header::ACCEPT_LANGUAGE,
+    middleware::Next,
+    response::Response,
+};
+use axum_login::AuthSession;
+use fluent::{
+    concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue,
+};
+use fluent_langneg::{
+    accepted_languages, negotiate_languages, NegotiationStrategy,
+};
+use tracing::{instrument, warn};
+use unic_langid::LanguageIdentifier;
+
+use super::{authenticator::AuthenticatorService, state::StateService};
+
+const CATALOGS: [(&str, &str); 2] = [
+    ("en", include_str!("../../locales/en.ftl")),
+    ("hu", include_str!("../../locales/hu.ftl")),
+];
+
+pub struct I18nService {
+    languages: Vec<LanguageIdentifier>,
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LocaleService {
+    i18n: Arc<I18nService>,
+    index: usize,
+    preference: Option<String>,
+}
+
+impl Debug for I18nService {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("I18nService")
+            .field("languages", &self.languages)
+            .finish()
+    }
+}
+
+impl I18nService {
+    pub const LOCALES: [&str; 2] = [CATALOGS[0].0, CATALOGS[1].0];
+
+    #[instrument(level = "debug")]
+    pub fn new() -> Result<Self, String> {
+        let mut languages = Vec::new();
+        let mut bundles = Vec::new();
+        for (locale, catalog) in CATALOGS {
+            let language = locale
+                .parse::<LanguageIdentifier>()
+                .map_err(|error| format!("{locale}: {error}"))?;
+            let resource = FluentResource::try_new(catalog.to_owned())
+                .map_err(|(_, errors)| format!("{locale}: {errors:?}"))?;
+            let mut bundle =
+                FluentBundle::new_concurrent(vec![language.clone()]);
+            bundle.set_use_isolating(false);
+            bundle
+                .add_resource(resource)
+                .map_err(|errors| format!("{locale}: {errors:?}"))?;
+            languages.push(language);
+            bundles.push(bundle);
+        }
+        Ok(Self { languages, bundles })
+    }
+
+    fn negotiate(
+        &self,
+        preference: Option<&str>,
+        accept_language: Option<&str>,
+    ) -> usize {
+        let requested = preference
+            .and_then(|preference| preference.parse().ok())
+            .into_iter()
+            .chain(
+                accept_language
+                    .map(accepted_languages::parse)
+                    .into_iter()
+                    .flatten(),
+            )
+            .collect::<Vec<LanguageIdentifier>>();
+        negotiate_languages(
+            &requested,
+            &self.languages,
+            None,
+            NegotiationStrategy::Lookup,
+        )
+        .first()
+        .and_then(|language| {
+            self.languages.iter().position(|other| other == *language)
+        })
+        .unwrap_or_default()
+    }
+
+    fn format(
+        &self,
+        index: usize,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> String {
+        [index, 0]
+            .into_iter()
+            .find_map(|index| {
+                let bundle = &self.bundles[index];
+                let pattern = bundle.get_message(key)?.value()?;
+                let mut errors = Vec::new();
+                let message = bundle.format_pattern(pattern, args, &mut errors);
+                if !errors.is_empty() {
+                    warn!("{key}: {errors:?}");
+                }
+                Some(message.into_owned())
+            })
+            .unwrap_or_else(|| {
+                warn!("{key}: missing message");
+                key.to_owned()
+            })
+    }
+}
+
+impl LocaleService {
+    pub fn new(
+        i18n: Arc<I18nService>,
+        preference: Option<&str>,
+        accept_language: Option<&str>,
+    ) -> Self {
+        Self {
+            index: i18n.negotiate(preference, accept_language),
+            preference: preference.map(str::to_owned),
+            i18n,
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        I18nService::LOCALES[self.index]
+    }
+
+    pub fn preference(&self) -> Option<&str> {
+        self.preference.as_deref()
+    }
+
+    pub fn selected(&self, locale: &str) -> bool {
+        self.preference() == Some(locale)
+    }
+
+    pub fn locales(&self) -> Vec<(&'static str, String)> {
+        I18nService::LOCALES
+            .into_iter()
+            .enumerate()
+            .map(|(index, locale)| {
+                (locale, self.i18n.format(index, "locale-name", None))
+            })
+            .collect()
+    }
+
+    pub fn get(&self, key: &str) -> String {
+        self.i18n.format(self.index, key, None)
+    }
+
+    pub fn format(&self, key: &str, args: &[(&str, FluentValue)]) -> String {
+        let args = args.iter().cloned().collect::<FluentArgs>();
+        self.i18n.format(self.index, key, Some(&args))
+    }
+}
+
+#[instrument(level = "trace", skip(state, authenticator))]
+pub async fn locale_service(
+    State(state): State<Arc<StateService>>,
+    authenticator: AuthSession<AuthenticatorService>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let locale = LocaleService::new(
+        state.i18n().clone(),
+        authenticator
+            .user
+            .as_ref()
+            .and_then(|user| user.locale.as_deref()),
+        request
+            .headers()
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|accept_language| accept_language.to_str().ok()),
+    );
+    request.extensions_mut().insert(locale);
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeSet,
+        fs::{read_dir, read_to_string},
+        path::Path,
+    };
+
+    use fluent::fluent_args;
+    use time::OffsetDateTime;
+
+    use super::*;
+    use crate::models::{
+        audit::AuditEventModel, notification::NotificationModel,
+        reaction::ReactionModel,
+    };
+
+    fn keys(catalog: &str) -> BTreeSet<String> {
+        catalog
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| Some(line.split_once('=')?.0.trim().to_owned()))
+            .collect()
+    }
+
+    fn sources(path: &Path, sources: &mut Vec<String>) {
+        for entry in read_dir(path).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                self::sources(&path, sources);
+            } else {
+                sources.push(read_to_string(path).unwrap());
+            }
+        }
+    }
+
+    fn notification(kind: &str, detail: Option<&str>) -> NotificationModel {
+        NotificationModel {
+            id: 0,
+            user_name: String::new(),
+            kind: kind.to_owned(),
+            actor: None,
+            message_id: None,
+            detail: detail.map(str::to_owned),
+            seen: false,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn referenced() -> BTreeSet<String> {
+        let mut templates = Vec::new();
+        sources(Path::new("templates"), &mut templates);
+        let mut code = Vec::new();
+        sources(Path::new("src"), &mut code);
+        let namespaces = keys(CATALOGS[0].1)
+            .into_iter()
+            .filter_map(|key| Some(key.split_once('-')?.0.to_owned() + "-"))
+            .collect::<BTreeSet<_>>();
+        let mut referenced = BTreeSet::new();
+        for template in templates {
+            for (end, _) in template.match_indices("\"|t") {
+                if let Some(start) = template[..end].rfind('"') {
+                    referenced.insert(template[start + 1..end].to_owned());
+                }
+            }
+        }
+        for code in &code {
+            for attribute in code.split("#[validate(").skip(1) {
+                let field = attribute
+                    .split_once("pub ")
+                    .and_then(|(_, field)| field.split_once(':'));
+                if let Some((field, _)) = field {
+                    if !field.contains(char::is_whitespace) {
+                        referenced.insert(format!("field-{field}"));
+                    }
+                }
+            }
+        }
+        for (reaction, _) in ReactionModel::REACTIONS {
+            referenced.insert(format!("reaction-{reaction}"));
+        }
+        for kind in NotificationModel::KINDS {
+            referenced.insert(format!("notification-preference-{kind}"));
+            if kind != NotificationModel::ADMIN {
+                referenced.insert(notification(kind, None).key());
+            }
+        }
+        for detail in [
+            AuditEventModel::MESSAGE_PIN,
+            AuditEventModel::MESSAGE_UNPIN,
+            AuditEventModel::MESSAGE_DELETE,
+            AuditEventModel::USER_SET_PASSWORD,
+            AuditEventModel::USER_SET_ROLE,
+            AuditEventModel::USER_ENABLE,
+            AuditEventModel::USER_ISSUE_TOKEN,
+            AuditEventModel::USER_REVOKE_TOKEN,
+        ] {
+            referenced.insert(
+                notification(NotificationModel::ADMIN, Some(detail)).key(),
+            );
+        }
+        for code in code {
+            for literal in code.split('"').skip(1).step_by(2) {
+                if namespaces
+                    .iter()
+                    .any(|namespace| literal.starts_with(namespace.as_str()))
+                    && literal
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c == '-')
+                {
+                    referenced.insert(literal.to_owned());
+                }
+            }
+        }
+        referenced
+    }
+
+    #[test]
+    fn catalogs_load() {
+        assert!(I18nService::new().is_ok());
+    }
+
+    #[test]
+    fn catalogs_have_no_missing_keys() {
+        let english = keys(CATALOGS[0].1);
+        for (locale, catalog) in CATALOGS {
+            let keys = keys(catalog);
+            let missing = english.difference(&keys).collect::<Vec<_>>();
+            assert!(missing.is_empty(), "{locale} is missing {missing:?}");
+            let extra = keys.difference(&english).collect::<Vec<_>>();
+            assert!(extra.is_empty(), "{locale} has unknown {extra:?}");
+        }
+    }
+
+    #[test]
+    fn referenced_keys_exist() {
+        let referenced = referenced();
+        assert!(referenced.contains("nav-logout"));
+        assert!(referenced.contains("toast-message-sent"));
+        assert!(referenced.contains("field-title"));
+        assert!(referenced.contains("field-from"));
+        assert!(referenced.contains("reaction-like"));
+        assert!(referenced.contains("notification-mention"));
+        assert!(referenced.contains("notification-admin-user_set_role"));
+        for (locale, catalog) in CATALOGS {
+            let keys = keys(catalog);
+            let missing = referenced.difference(&keys).collect::<Vec<_>>();
+            assert!(missing.is_empty(), "{locale} is missing {missing:?}");
+        }
+    }
+
+    #[test]
+    fn negotiates_accept_language() {
+        let i18n = Arc::new(I18nService::new().unwrap());
+        let locale = |accept_language| {
+            LocaleService::new(i18n.clone(), None, Some(accept_language))
+                .language()
+                .to_owned()
+        };
+        assert_eq!(locale("hu-HU,hu;q=0.9,en;q=0.8"), "hu");
+        assert_eq!(locale("de-DE,en;q=0.5"), "en");
+        assert_eq!(locale("de-DE"), "en");
+        assert_eq!(locale(""), "en");
+    }
+
+    #[test]
+    fn preference_overrides_accept_language() {
+        let i18n = Arc::new(I18nService::new().unwrap());
+        let locale = LocaleService::new(i18n.clone(), Some("hu"), Some("en"));
+        assert_eq!(locale.language(), "hu");
+        let locale = LocaleService::new(i18n, Some("xx"), Some("hu"));
+        assert_eq!(locale.language(), "hu");
+    }
+
+    #[test]
+    fn formats_arguments() {
+        let i18n = Arc::new(I18nService::new().unwrap());
+        let locale = LocaleService::new(i18n.clone(), Some("en"), None);
+        assert_eq!(
+            locale.format("toast-message-sent", &[("id", 7.into())]),
+            "Message #7 sent."
+        );
+        let args = fluent_args!["field" => "Title", "min" => 1];
+        assert_eq!(
+            i18n.format(0, "validation-min-length", Some(&args)),
+            "Title must be at least 1 character long."
+        );
+        assert_eq!(locale.get("missing-key"), "missing-key");
+    }
+}
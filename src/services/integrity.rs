@@ -3,7 +3,7 @@ use std::sync::Arc;
 use axum::{
     body::Body,
     extract::Request,
-    http::{Method, StatusCode},
+    http::{header::AUTHORIZATION, Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -22,7 +22,8 @@ pub async fn integrity_service(
     if matches!(
         *request.method(),
         Method::POST | Method::PUT | Method::DELETE | Method::PATCH
-    ) {
+    ) && !bearer(&request)
+    {
         if request.headers().get("HX-Request").is_none() {
             warn!("missing HX-Request header");
             return Err(
@@ -56,3 +57,12 @@ pub async fn integrity_service(
     }
     Ok(next.run(request).await)
 }
+
+fn bearer(request: &Request<Body>) -> bool {
+    request.uri().path().starts_with("/api/")
+        && request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|authorization| authorization.to_str().ok())
+            .is_some_and(|authorization| authorization.starts_with("Bearer "))
+}
@@ -0,0 +1,282 @@
+use std::{convert::Infallible, sync::Arc};
+
+use axum::{
+    extract::FromRequestParts, http::request::Parts, response::sse::Event,
+};
+use metrics::counter;
+use tracing::instrument;
+
+use crate::{
+    error::AppError,
+    models::{
+        audit::AuditEventModel, channel::ChannelModel, mention::MentionModel,
+        message::MessageModel, notification::NotificationModel, tag::TagModel,
+    },
+};
+
+use super::{
+    audit::AuditService,
+    notification::NotificationService,
+    state::{MessageEvent, StateService},
+};
+
+#[derive(Debug, Clone)]
+pub struct MessageService {
+    state: Arc<StateService>,
+    audit: AuditService,
+    notifications: NotificationService,
+}
+
+impl FromRequestParts<Arc<StateService>> for MessageService {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<StateService>,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            state: state.clone(),
+            audit: AuditService::from_request_parts(parts, state).await?,
+            notifications: NotificationService::from_request_parts(
+                parts, state,
+            )
+            .await?,
+        })
+    }
+}
+
+impl MessageService {
+    #[instrument(level = "debug", skip(state))]
+    pub async fn channel(
+        state: &StateService,
+        user: &str,
+        id: i32,
+    ) -> Result<i32, AppError> {
+        ChannelModel::accessible(state.database(), id, user)
+            .await?
+            .and_then(|channel| channel.id)
+            .ok_or(AppError::NotFound)
+    }
+
+    #[instrument(level = "debug", skip(state))]
+    pub async fn message(
+        state: &StateService,
+        user: &str,
+        id: i32,
+    ) -> Result<MessageModel, AppError> {
+        let message = MessageModel::find(state.database(), id)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        Self::channel(state, user, message.channel_id).await?;
+        Ok(message)
+    }
+
+    #[instrument(level = "debug", skip(self, message))]
+    pub async fn create(
+        &self,
+        actor: &str,
+        message: MessageModel,
+    ) -> Result<MessageModel, AppError> {
+        let parent = match message.parent_id {
+            Some(parent) => {
+                Some(Self::message(&self.state, actor, parent).await?)
+            }
+            None => None,
+        };
+        let channel_id = match &parent {
+            Some(parent) => parent.channel_id,
+            None => {
+                Self::channel(&self.state, actor, message.channel_id).await?
+            }
+        };
+        let mut transaction = self.state.database().begin().await?;
+        let id = MessageModel::create(
+            &mut transaction,
+            channel_id,
+            message.parent_id,
+            actor,
+            &message.title,
+            &message.content,
+        )
+        .await?;
+        TagModel::set(&mut transaction, id, &message.tags).await?;
+        transaction.commit().await?;
+        let mentions = self
+            .mention(actor, id, channel_id, &message.content)
+            .await?;
+        if let Some(parent) = &parent {
+            self.replied(parent, actor, &mentions).await?;
+        }
+        self.audit
+            .record(
+                self.state.database(),
+                Some(actor),
+                AuditEventModel::MESSAGE_CREATE,
+                Some(&format!("message/{id}")),
+            )
+            .await;
+        let event = if let Some(parent) = message.parent_id {
+            counter!("messages_total", "action" => "reply").increment(1);
+            format!("reply{parent}")
+        } else {
+            counter!("messages_total", "action" => "create").increment(1);
+            "create".to_owned()
+        };
+        let message = MessageModel {
+            id: Some(id),
+            channel_id,
+            author: Some(actor.to_owned()),
+            replies: 0,
+            reactions: Vec::new(),
+            mentions,
+            ..message
+        };
+        self.state.messages(channel_id).send((
+            Event::default()
+                .id(self.state.id().to_string())
+                .event(event),
+            MessageEvent::Message(message.clone()),
+        ))?;
+        self.refresh(message.parent_id).await?;
+        Ok(message)
+    }
+
+    #[instrument(level = "debug", skip(self, message))]
+    pub async fn update(
+        &self,
+        actor: &str,
+        id: i32,
+        message: MessageModel,
+    ) -> Result<MessageModel, AppError> {
+        let original = Self::message(&self.state, actor, id).await?;
+        let mut transaction = self.state.database().begin().await?;
+        MessageModel::update(
+            &mut transaction,
+            id,
+            &message.title,
+            &message.content,
+        )
+        .await?;
+        TagModel::set(&mut transaction, id, &message.tags).await?;
+        transaction.commit().await?;
+        self.mention(actor, id, original.channel_id, &message.content)
+            .await?;
+        self.notifications
+            .author(&original, NotificationModel::EDIT, actor, None)
+            .await?;
+        self.audit
+            .record(
+                self.state.database(),
+                Some(actor),
+                AuditEventModel::MESSAGE_UPDATE,
+                Some(&format!("message/{id}")),
+            )
+            .await;
+        counter!("messages_total", "action" => "update").increment(1);
+        self.refresh(Some(id)).await?.ok_or(AppError::NotFound)
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    pub async fn delete(&self, actor: &str, id: i32) -> Result<(), AppError> {
+        let message = Self::message(&self.state, actor, id).await?;
+        self.notifications.deleted(&message, actor).await?;
+        if MessageModel::delete(self.state.database(), id)
+            .await?
+            .rows_affected()
+            == 0
+        {
+            return Err(AppError::NotFound);
+        }
+        self.audit
+            .record(
+                self.state.database(),
+                Some(actor),
+                AuditEventModel::MESSAGE_DELETE,
+                Some(&format!("message/{id}")),
+            )
+            .await;
+        counter!("messages_total", "action" => "delete").increment(1);
+        self.state.messages(message.channel_id).send((
+            Event::default()
+                .id(self.state.id().to_string())
+                .event(format!("destroy{id}")),
+            MessageEvent::Deleted,
+        ))?;
+        self.refresh(message.parent_id).await?;
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn refresh(
+        &self,
+        id: Option<i32>,
+    ) -> Result<Option<MessageModel>, AppError> {
+        let Some(id) = id else {
+            return Ok(None);
+        };
+        let message = MessageModel::find(self.state.database(), id).await?;
+        if let Some(message) = &message {
+            self.state.messages(message.channel_id).send((
+                Event::default()
+                    .id(self.state.id().to_string())
+                    .event(format!("update{id}")),
+                MessageEvent::Message(message.clone()),
+            ))?;
+        }
+        Ok(message)
+    }
+
+    #[instrument(level = "debug", skip(self, content))]
+    async fn mention(
+        &self,
+        actor: &str,
+        id: i32,
+        channel_id: i32,
+        content: &str,
+    ) -> Result<Vec<String>, AppError> {
+        let database = self.state.database();
+        let previous = MentionModel::message(database, id).await?;
+        let mentions = MentionModel::resolve(database, content).await?;
+        MentionModel::set(database, id, &mentions).await?;
+        for name in &mentions {
+            if name == actor
+                || previous.contains(name)
+                || ChannelModel::accessible(database, channel_id, name)
+                    .await?
+                    .is_none()
+            {
+                continue;
+            }
+            self.notifications
+                .notify(
+                    name,
+                    NotificationModel::MENTION,
+                    Some(actor),
+                    None,
+                    Some(id),
+                )
+                .await?;
+        }
+        Ok(mentions)
+    }
+
+    #[instrument(level = "debug", skip(self, parent))]
+    async fn replied(
+        &self,
+        parent: &MessageModel,
+        actor: &str,
+        mentions: &[String],
+    ) -> Result<(), AppError> {
+        if parent
+            .author
+            .as_ref()
+            .is_some_and(|author| mentions.contains(author))
+        {
+            return Ok(());
+        }
+        self.notifications
+            .author(parent, NotificationModel::REPLY, actor, None)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use sqlx::{
+    migrate,
+    migrate::{Migrate, MigrateError, Migration, Migrator},
+    query_scalar,
+};
+use tracing::{info, instrument};
+
+use crate::database::DatabasePool;
+
+#[cfg(feature = "mysql")]
+static MIGRATOR: Migrator = migrate!("./migrations/mysql");
+
+#[cfg(feature = "postgres")]
+static MIGRATOR: Migrator = migrate!("./migrations/postgres");
+
+#[cfg(feature = "sqlite")]
+static MIGRATOR: Migrator = migrate!("./migrations/sqlite");
+
+#[derive(Debug)]
+pub struct MigratorService;
+
+#[derive(Debug)]
+pub enum MigratorError {
+    Database(sqlx::Error),
+    Migrate(MigrateError),
+    Pending(Vec<i64>),
+    Unknown(Vec<i64>),
+}
+
+impl Display for MigratorError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(error) => write!(formatter, "{error}"),
+            Self::Migrate(error) => write!(formatter, "{error}"),
+            Self::Pending(versions) => write!(
+                formatter,
+                "pending migrations {versions:?}, run `rustweb migrate` \
+                 or enable database.migrate"
+            ),
+            Self::Unknown(versions) => write!(
+                formatter,
+                "database schema is newer than this binary, unknown \
+                 migrations {versions:?}"
+            ),
+        }
+    }
+}
+
+impl Error for MigratorError {}
+
+impl From<sqlx::Error> for MigratorError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+impl From<MigrateError> for MigratorError {
+    fn from(error: MigrateError) -> Self {
+        Self::Migrate(error)
+    }
+}
+
+impl MigratorService {
+    #[instrument(level = "debug")]
+    pub async fn pending(
+        database: &DatabasePool,
+    ) -> Result<Vec<&'static Migration>, MigratorError> {
+        let applied: HashSet<i64> =
+            query_scalar("SELECT version FROM _sqlx_migrations;")
+                .fetch_all(database)
+                .await?
+                .into_iter()
+                .collect();
+        let mut unknown = applied
+            .iter()
+            .filter(|version| {
+                !MIGRATOR
+                    .iter()
+                    .any(|migration| migration.version == **version)
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            unknown.sort_unstable();
+            return Err(MigratorError::Unknown(unknown));
+        }
+        Ok(MIGRATOR
+            .iter()
+            .filter(|migration| {
+                !migration.migration_type.is_down_migration()
+                    && !applied.contains(&migration.version)
+            })
+            .collect())
+    }
+
+    #[instrument(level = "debug")]
+    pub async fn migrate(
+        database: &DatabasePool,
+        apply: bool,
+    ) -> Result<(), MigratorError> {
+        database.acquire().await?.ensure_migrations_table().await?;
+        let pending = Self::pending(database).await?;
+        if pending.is_empty() {
+            info!("database schema is up to date");
+            return Ok(());
+        }
+        if !apply {
+            return Err(MigratorError::Pending(
+                pending.iter().map(|migration| migration.version).collect(),
+            ));
+        }
+        MIGRATOR.run(database).await?;
+        for migration in pending {
+            info!(
+                "applied migration {} {}",
+                migration.version, migration.description
+            );
+        }
+        Ok(())
+    }
+}
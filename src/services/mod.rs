@@ -1,3 +1,15 @@
+pub mod accept;
+pub mod api;
+pub mod audit;
 pub mod authenticator;
+pub mod error;
+pub mod i18n;
 pub mod integrity;
+pub mod message;
+pub mod metrics;
+pub mod migrator;
+pub mod notification;
+pub mod span;
 pub mod state;
+#[cfg(feature = "otel")]
+pub mod telemetry;
@@ -0,0 +1,103 @@
+use std::{convert::Infallible, sync::Arc};
+
+use axum::{
+    extract::FromRequestParts, http::request::Parts, response::sse::Event,
+};
+use metrics::counter;
+use sqlx::Error;
+use tracing::instrument;
+
+use crate::models::{
+    audit::AuditEventModel, message::MessageModel,
+    notification::NotificationModel,
+};
+
+use super::state::{StateService, UserEvent};
+
+#[derive(Debug, Clone)]
+pub struct NotificationService {
+    state: Arc<StateService>,
+}
+
+impl FromRequestParts<Arc<StateService>> for NotificationService {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        _: &mut Parts,
+        state: &Arc<StateService>,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            state: state.clone(),
+        })
+    }
+}
+
+impl NotificationService {
+    #[instrument(level = "trace", skip(self))]
+    pub async fn notify(
+        &self,
+        user: &str,
+        kind: &'static str,
+        actor: Option<&str>,
+        detail: Option<&str>,
+        message_id: Option<i32>,
+    ) -> Result<(), Error> {
+        let database = self.state.database();
+        if !NotificationModel::send(
+            database, user, kind, actor, detail, message_id,
+        )
+        .await?
+        {
+            return Ok(());
+        }
+        counter!("notifications_total", "kind" => kind).increment(1);
+        self.state.notify(
+            user,
+            (
+                Event::default()
+                    .id(self.state.id().to_string())
+                    .event("notification"),
+                UserEvent::Notification,
+            ),
+        );
+        Ok(())
+    }
+
+    #[instrument(level = "trace", skip(self, message))]
+    pub async fn author(
+        &self,
+        message: &MessageModel,
+        kind: &'static str,
+        actor: &str,
+        detail: Option<&str>,
+    ) -> Result<(), Error> {
+        match message.author.as_deref() {
+            Some(author) if author != actor => {
+                self.notify(author, kind, Some(actor), detail, message.id)
+                    .await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    #[instrument(level = "trace", skip(self, message))]
+    pub async fn deleted(
+        &self,
+        message: &MessageModel,
+        actor: &str,
+    ) -> Result<(), Error> {
+        match message.author.as_deref() {
+            Some(author) if author != actor => {
+                self.notify(
+                    author,
+                    NotificationModel::ADMIN,
+                    Some(actor),
+                    Some(AuditEventModel::MESSAGE_DELETE),
+                    None,
+                )
+                .await
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use axum::{
+    body::Body, extract::Request, middleware::Next, response::Response,
+};
+use axum_login::AuthSession;
+use tracing::Span;
+
+use super::authenticator::AuthenticatorService;
+
+pub async fn span_service(mut request: Request<Body>, next: Next) -> Response {
+    request.extensions_mut().insert(Span::current());
+    next.run(request).await
+}
+
+pub async fn span_user_service(
+    authenticator: AuthSession<AuthenticatorService>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if let (Some(span), Some(user)) =
+        (request.extensions().get::<Span>(), &authenticator.user)
+    {
+        span.record("user", &user.name);
+    }
+    next.run(request).await
+}
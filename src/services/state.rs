@@ -1,26 +1,76 @@
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::{Duration, Instant},
+};
 
 use axum::response::sse::Event;
-use sqlx::{Error, MySqlPool};
-use tokio::sync::broadcast::{channel, Sender};
+use metrics_exporter_prometheus::PrometheusHandle;
+use sqlx::Error;
+use tokio::sync::broadcast::{channel, Receiver, Sender};
 use tracing::instrument;
 
-use crate::models::message::MessageModel;
+use crate::{
+    config::Config,
+    database::DatabasePool,
+    models::{direct_message::DirectMessageModel, message::MessageModel},
+};
+
+use super::i18n::I18nService;
 
 #[derive(Debug)]
 pub struct StateService {
     id: AtomicU64,
-    database: MySqlPool,
-    messages: Sender<(Event, Option<MessageModel>)>,
+    started: Instant,
+    config: Config,
+    database: DatabasePool,
+    metrics: PrometheusHandle,
+    i18n: Arc<I18nService>,
+    channels: Mutex<HashMap<i32, MessageChannel>>,
+    users: Mutex<HashMap<String, UserChannel>>,
+}
+
+#[derive(Clone, Debug)]
+pub enum MessageEvent {
+    Message(MessageModel),
+    Reactions(MessageModel),
+    Pins(Vec<MessageModel>),
+    Deleted,
 }
 
+#[derive(Clone, Debug)]
+pub enum UserEvent {
+    DirectMessage(DirectMessageModel),
+    Unread,
+    Notification,
+}
+
+type MessageChannel = (
+    Sender<(Event, MessageEvent)>,
+    Receiver<(Event, MessageEvent)>,
+);
+
+type UserChannel = Sender<(Event, UserEvent)>;
+
 impl StateService {
     #[instrument(level = "debug")]
-    pub async fn new(database: &str) -> Result<Self, Error> {
+    pub async fn new(
+        config: Config,
+        metrics: PrometheusHandle,
+        i18n: I18nService,
+    ) -> Result<Self, Error> {
         Ok(Self {
             id: AtomicU64::default(),
-            database: MySqlPool::connect(database).await?,
-            messages: channel(u8::MAX.into()).0,
+            started: Instant::now(),
+            database: DatabasePool::connect(&config.database_url).await?,
+            metrics,
+            i18n: Arc::new(i18n),
+            channels: Mutex::default(),
+            users: Mutex::default(),
+            config,
         })
     }
 
@@ -28,11 +78,71 @@ impl StateService {
         self.id.fetch_add(1, Ordering::Relaxed)
     }
 
-    pub fn database(&self) -> &MySqlPool {
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn database(&self) -> &DatabasePool {
         &self.database
     }
 
-    pub fn messages(&self) -> &Sender<(Event, Option<MessageModel>)> {
-        &self.messages
+    pub fn metrics(&self) -> &PrometheusHandle {
+        &self.metrics
+    }
+
+    pub fn i18n(&self) -> &Arc<I18nService> {
+        &self.i18n
+    }
+
+    pub fn messages(&self, channel_id: i32) -> Sender<(Event, MessageEvent)> {
+        self.channels
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(channel_id)
+            .or_insert_with(|| channel(self.config.messages_capacity))
+            .0
+            .clone()
+    }
+
+    pub fn subscribe(&self, user: &str) -> Receiver<(Event, UserEvent)> {
+        self.users
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(user.to_owned())
+            .or_insert_with(|| channel(self.config.messages_capacity).0)
+            .subscribe()
+    }
+
+    pub fn notify(&self, user: &str, event: (Event, UserEvent)) {
+        let mut users =
+            self.users.lock().unwrap_or_else(PoisonError::into_inner);
+        if users
+            .get(user)
+            .is_some_and(|sender| sender.send(event).is_err())
+        {
+            users.remove(user);
+        }
+    }
+
+    pub fn subscribers(&self) -> usize {
+        let messages: usize = self
+            .channels
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .map(|(messages, _)| messages.receiver_count().saturating_sub(1))
+            .sum();
+        let users: usize = self
+            .users
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .map(Sender::receiver_count)
+            .sum();
+        messages + users
     }
 }
@@ -0,0 +1,125 @@
+use axum::http::HeaderMap;
+use opentelemetry::{global, trace::TracerProvider, Context};
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_otlp::{ExporterBuildError, SpanExporter};
+use opentelemetry_sdk::{
+    error::OTelSdkResult,
+    propagation::TraceContextPropagator,
+    trace::{SdkTracer, SdkTracerProvider},
+    Resource,
+};
+use tracing::{instrument, Subscriber};
+use tracing_opentelemetry::{layer, OpenTelemetryLayer};
+use tracing_subscriber::registry::LookupSpan;
+
+#[derive(Debug)]
+pub struct TelemetryService(SdkTracerProvider);
+
+impl TelemetryService {
+    pub fn install() -> Result<Self, ExporterBuildError> {
+        let telemetry = Self::new(SpanExporter::builder().with_http().build()?);
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        global::set_tracer_provider(telemetry.0.clone());
+        Ok(telemetry)
+    }
+
+    fn new(exporter: SpanExporter) -> Self {
+        Self(
+            SdkTracerProvider::builder()
+                .with_batch_exporter(exporter)
+                .with_resource(
+                    Resource::builder()
+                        .with_service_name(env!("CARGO_PKG_NAME"))
+                        .build(),
+                )
+                .build(),
+        )
+    }
+
+    pub fn layer<S>(&self) -> OpenTelemetryLayer<S, SdkTracer>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        layer().with_tracer(self.0.tracer(env!("CARGO_PKG_NAME")))
+    }
+
+    #[instrument(level = "debug")]
+    pub fn shutdown(&self) -> OTelSdkResult {
+        self.0.shutdown()
+    }
+
+    pub fn context(headers: &HeaderMap) -> Context {
+        global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(headers))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        error::Error,
+        io::{self, Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use opentelemetry_otlp::WithExportConfig;
+    use tracing::{info_span, subscriber::with_default};
+    use tracing_subscriber::{layer::SubscriberExt, registry};
+
+    use super::*;
+
+    fn complete(request: &[u8]) -> bool {
+        let text = String::from_utf8_lossy(request);
+        let Some(end) = text.find("\r\n\r\n") else {
+            return false;
+        };
+        let length = text[..end]
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+            .unwrap_or_default();
+        end + 4 + length <= request.len()
+    }
+
+    #[test]
+    fn exports_spans_to_the_configured_endpoint() -> Result<(), Box<dyn Error>>
+    {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let endpoint = format!("http://{}/v1/traces", listener.local_addr()?);
+        let collector = thread::spawn(move || -> io::Result<Vec<u8>> {
+            let (mut stream, _) = listener.accept()?;
+            stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            while !complete(&request) {
+                let read = stream.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")?;
+            Ok(request)
+        });
+        let telemetry = TelemetryService::new(
+            SpanExporter::builder()
+                .with_http()
+                .with_endpoint(endpoint)
+                .build()?,
+        );
+        with_default(registry().with(telemetry.layer()), || {
+            info_span!("telemetry_probe").in_scope(|| {});
+        });
+        telemetry.shutdown()?;
+        let request = collector.join().map_err(|_| "collector panicked")??;
+        let request = String::from_utf8_lossy(&request);
+        assert!(request.starts_with("POST /v1/traces "));
+        assert!(request.contains("telemetry_probe"));
+        Ok(())
+    }
+}
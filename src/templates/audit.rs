@@ -0,0 +1,52 @@
+use askama::Template;
+
+use crate::{
+    models::{audit::AuditEventModel, user::UserModel},
+    services::i18n::LocaleService,
+};
+
+use super::filters;
+
+#[derive(Template)]
+#[template(path = "./audit.html")]
+pub struct AuditTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    location: &'a str,
+    name: Option<&'a str>,
+    admin: bool,
+    actions: &'a [&'a str],
+}
+
+impl<'a> AuditTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        user: &'a UserModel,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            location: "location-audit",
+            name: Some(&user.name),
+            admin: user.is_admin(),
+            actions: &AuditEventModel::ACTIONS,
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./audit/events.html")]
+pub struct AuditEventsTemplate<'a> {
+    locale: &'a LocaleService,
+    events: &'a Vec<AuditEventModel>,
+}
+
+impl<'a> AuditEventsTemplate<'a> {
+    pub fn new(
+        locale: &'a LocaleService,
+        events: &'a Vec<AuditEventModel>,
+    ) -> Self {
+        Self { locale, events }
+    }
+}
@@ -1,23 +1,38 @@
 use askama::Template;
 
+use crate::{services::i18n::LocaleService, validation::FieldErrors};
+
+use super::{
+    filters,
+    form::{FormFieldTemplate, FormTemplate},
+};
+
 #[derive(Template)]
 #[template(path = "./authentication.html")]
 pub struct AuthenticationTemplate<'a> {
     token: &'a str,
+    locale: &'a LocaleService,
     location: &'a str,
     name: Option<&'a str>,
+    admin: bool,
     login_form: AuthenticationLoginFormTemplate<'a>,
     register_form: AuthenticationRegisterFormTemplate<'a>,
 }
 
 impl<'a> AuthenticationTemplate<'a> {
-    pub fn new(token: &'a str) -> Self {
+    pub fn new(token: &'a str, locale: &'a LocaleService) -> Self {
         Self {
             token,
-            location: "Authentication",
+            locale,
+            location: "location-authentication",
             name: None,
-            login_form: AuthenticationLoginFormTemplate::new(token, false),
-            register_form: AuthenticationRegisterFormTemplate::new(token),
+            admin: false,
+            login_form: AuthenticationLoginFormTemplate::new(
+                token, locale, false,
+            ),
+            register_form: AuthenticationRegisterFormTemplate::new(
+                token, locale, "", "",
+            ),
         }
     }
 }
@@ -26,96 +41,95 @@ impl<'a> AuthenticationTemplate<'a> {
 #[template(path = "./authentication/login_form.html")]
 pub struct AuthenticationLoginFormTemplate<'a> {
     token: &'a str,
-    form_name: AuthenticationFormNameTemplate<'a>,
-    form_password: AuthenticationFormPasswordTemplate<'a>,
+    locale: &'a LocaleService,
+    name: FormFieldTemplate<'a>,
+    password: FormFieldTemplate<'a>,
     error: bool,
 }
 
 impl<'a> AuthenticationLoginFormTemplate<'a> {
-    pub fn new(token: &'a str, error: bool) -> Self {
+    pub fn new(token: &'a str, locale: &'a LocaleService, error: bool) -> Self {
         Self {
             token,
-            form_name: AuthenticationFormNameTemplate::new(token, false),
-            form_password: AuthenticationFormPasswordTemplate::new(
-                token, false,
-            ),
+            locale,
+            name: FormFieldTemplate::new(
+                token,
+                locale,
+                "login",
+                "name",
+                "field-name",
+            )
+            .autocomplete("username"),
+            password: FormFieldTemplate::new(
+                token,
+                locale,
+                "login",
+                "password",
+                "field-password",
+            )
+            .password(),
             error,
         }
     }
 
     pub fn validate(mut self, error: Option<&'a str>) -> Self {
-        self.form_name = self.form_name.validate("", error);
-        self.form_password = self.form_password.validate("", error);
+        self.name = self.name.error(error);
+        self.password = self.password.error(error);
         self
     }
 }
 
 #[derive(Template)]
 #[template(path = "./authentication/register_form.html")]
-struct AuthenticationRegisterFormTemplate<'a> {
+pub struct AuthenticationRegisterFormTemplate<'a> {
     token: &'a str,
-    form_name: AuthenticationFormNameTemplate<'a>,
-    form_password: AuthenticationFormPasswordTemplate<'a>,
+    locale: &'a LocaleService,
+    name: FormFieldTemplate<'a>,
+    password: FormFieldTemplate<'a>,
 }
 
 impl<'a> AuthenticationRegisterFormTemplate<'a> {
-    fn new(token: &'a str) -> Self {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        name: &'a str,
+        password: &'a str,
+    ) -> Self {
         Self {
             token,
-            form_name: AuthenticationFormNameTemplate::new(token, true),
-            form_password: AuthenticationFormPasswordTemplate::new(token, true),
+            locale,
+            name: FormFieldTemplate::new(
+                token,
+                locale,
+                "register",
+                "name",
+                "field-name",
+            )
+            .autocomplete("username")
+            .action("/validate")
+            .value(name),
+            password: FormFieldTemplate::new(
+                token,
+                locale,
+                "register",
+                "password",
+                "field-password",
+            )
+            .password()
+            .action("/validate")
+            .value(password),
         }
     }
-}
 
-#[derive(Template)]
-#[template(path = "./authentication/form_name.html")]
-pub struct AuthenticationFormNameTemplate<'a> {
-    token: &'a str,
-    validation: bool,
-    value: &'a str,
-    error: Option<&'a str>,
-}
-
-impl<'a> AuthenticationFormNameTemplate<'a> {
-    pub fn new(token: &'a str, validation: bool) -> Self {
-        Self {
-            token,
-            validation,
-            value: "",
-            error: None,
-        }
-    }
-
-    pub fn validate(mut self, value: &'a str, error: Option<&'a str>) -> Self {
-        self.value = value;
-        self.error = error;
+    pub fn validate(mut self, errors: &'a FieldErrors) -> Self {
+        self.name = self.name.validate(errors);
+        self.password = self.password.validate(errors);
         self
     }
 }
 
-#[derive(Template)]
-#[template(path = "./authentication/form_password.html")]
-pub struct AuthenticationFormPasswordTemplate<'a> {
-    token: &'a str,
-    validation: bool,
-    value: &'a str,
-    error: Option<&'a str>,
-}
-
-impl<'a> AuthenticationFormPasswordTemplate<'a> {
-    pub fn new(token: &'a str, validation: bool) -> Self {
-        Self {
-            token,
-            validation,
-            value: "",
-            error: None,
-        }
-    }
-
-    pub fn validate(mut self, value: &'a str, error: Option<&'a str>) -> Self {
-        self.value = value;
-        self.error = error;
-        self
+impl<'a> FormTemplate<'a> for AuthenticationRegisterFormTemplate<'a> {
+    fn fields(self) -> Vec<FormFieldTemplate<'a>> {
+        vec![self.name, self.password]
     }
 }
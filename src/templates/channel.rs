@@ -0,0 +1,114 @@
+use askama::Template;
+
+use crate::{services::i18n::LocaleService, validation::FieldErrors};
+
+use super::{
+    filters,
+    form::{FormFieldTemplate, FormTemplate},
+};
+
+#[derive(Template)]
+#[template(path = "./channel/form.html")]
+pub struct ChannelFormTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    name: FormFieldTemplate<'a>,
+}
+
+impl<'a> ChannelFormTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        name: &'a str,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            name: FormFieldTemplate::new(
+                token,
+                locale,
+                "channel",
+                "name",
+                "field-channel",
+            )
+            .value(name),
+        }
+    }
+
+    pub fn validate(mut self, errors: &'a FieldErrors) -> Self {
+        self.name = self.name.validate(errors);
+        self
+    }
+}
+
+impl<'a> FormTemplate<'a> for ChannelFormTemplate<'a> {
+    fn fields(self) -> Vec<FormFieldTemplate<'a>> {
+        vec![self.name]
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./channel/member_form.html")]
+pub struct ChannelMemberFormTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    id: i32,
+    name: FormFieldTemplate<'a>,
+}
+
+impl<'a> ChannelMemberFormTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        id: i32,
+        name: &'a str,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            id,
+            name: FormFieldTemplate::new(
+                token,
+                locale,
+                "member",
+                "name",
+                "field-name",
+            )
+            .value(name),
+        }
+    }
+
+    pub fn validate(mut self, errors: &'a FieldErrors) -> Self {
+        self.name = self.name.validate(errors);
+        self
+    }
+}
+
+impl<'a> FormTemplate<'a> for ChannelMemberFormTemplate<'a> {
+    fn fields(self) -> Vec<FormFieldTemplate<'a>> {
+        vec![self.name]
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./channel/members.html")]
+pub struct ChannelMembersTemplate<'a> {
+    locale: &'a LocaleService,
+    members: &'a [String],
+    oob: bool,
+}
+
+impl<'a> ChannelMembersTemplate<'a> {
+    pub fn new(locale: &'a LocaleService, members: &'a [String]) -> Self {
+        Self {
+            locale,
+            members,
+            oob: false,
+        }
+    }
+
+    pub fn oob(mut self) -> Self {
+        self.oob = true;
+        self
+    }
+}
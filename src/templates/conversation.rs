@@ -0,0 +1,218 @@
+use askama::Template;
+
+use crate::{
+    models::{
+        conversation::ConversationModel, direct_message::DirectMessageModel,
+        user::UserModel,
+    },
+    services::i18n::LocaleService,
+    validation::FieldErrors,
+};
+
+use super::{
+    filters,
+    form::{FormFieldTemplate, FormTemplate},
+};
+
+#[derive(Template)]
+#[template(path = "./conversation.html")]
+pub struct ConversationIndexTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    location: &'a str,
+    name: Option<&'a str>,
+    admin: bool,
+    conversations: &'a [ConversationModel],
+    form: ConversationFormTemplate<'a>,
+}
+
+impl<'a> ConversationIndexTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        user: &'a UserModel,
+        conversations: &'a [ConversationModel],
+        participants: &'a str,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            location: "location-conversations",
+            name: Some(&user.name),
+            admin: user.is_admin(),
+            conversations,
+            form: ConversationFormTemplate::new(
+                token,
+                locale,
+                participants,
+                "",
+            ),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./conversation/show.html")]
+pub struct ConversationShowTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    location: &'a str,
+    name: Option<&'a str>,
+    admin: bool,
+    conversation: &'a ConversationModel,
+    messages: Vec<DirectMessageTemplate<'a>>,
+    form: DirectMessageFormTemplate<'a>,
+}
+
+impl<'a> ConversationShowTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        user: &'a UserModel,
+        conversation: &'a ConversationModel,
+        messages: &'a [DirectMessageModel],
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            location: "location-conversations",
+            name: Some(&user.name),
+            admin: user.is_admin(),
+            conversation,
+            messages: messages
+                .iter()
+                .map(|message| DirectMessageTemplate::new(&user.name, message))
+                .collect(),
+            form: DirectMessageFormTemplate::new(
+                token,
+                locale,
+                conversation.id,
+                "",
+            ),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./conversation/form.html")]
+pub struct ConversationFormTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    participants: FormFieldTemplate<'a>,
+    content: FormFieldTemplate<'a>,
+}
+
+impl<'a> ConversationFormTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        participants: &'a str,
+        content: &'a str,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            participants: FormFieldTemplate::new(
+                token,
+                locale,
+                "conversation",
+                "participants",
+                "field-participants",
+            )
+            .value(participants),
+            content: FormFieldTemplate::new(
+                token,
+                locale,
+                "conversation",
+                "content",
+                "field-content",
+            )
+            .textarea()
+            .value(content),
+        }
+    }
+
+    pub fn validate(mut self, errors: &'a FieldErrors) -> Self {
+        self.participants = self.participants.validate(errors);
+        self.content = self.content.validate(errors);
+        self
+    }
+}
+
+impl<'a> FormTemplate<'a> for ConversationFormTemplate<'a> {
+    fn fields(self) -> Vec<FormFieldTemplate<'a>> {
+        vec![self.participants, self.content]
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./conversation/message_form.html")]
+pub struct DirectMessageFormTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    id: i32,
+    content: FormFieldTemplate<'a>,
+}
+
+impl<'a> DirectMessageFormTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        id: i32,
+        content: &'a str,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            id,
+            content: FormFieldTemplate::new(
+                token,
+                locale,
+                "direct",
+                "content",
+                "field-content",
+            )
+            .textarea()
+            .value(content),
+        }
+    }
+
+    pub fn validate(mut self, errors: &'a FieldErrors) -> Self {
+        self.content = self.content.validate(errors);
+        self
+    }
+}
+
+impl<'a> FormTemplate<'a> for DirectMessageFormTemplate<'a> {
+    fn fields(self) -> Vec<FormFieldTemplate<'a>> {
+        vec![self.content]
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./conversation/message.html")]
+pub struct DirectMessageTemplate<'a> {
+    own: bool,
+    message: &'a DirectMessageModel,
+}
+
+impl<'a> DirectMessageTemplate<'a> {
+    pub fn new(user: &str, message: &'a DirectMessageModel) -> Self {
+        Self {
+            own: message.sender == user,
+            message,
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./conversation/unread.html")]
+pub struct ConversationUnreadTemplate {
+    count: i64,
+}
+
+impl ConversationUnreadTemplate {
+    pub fn new(count: i64) -> Self {
+        Self { count }
+    }
+}
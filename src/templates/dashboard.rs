@@ -1,25 +1,71 @@
 use askama::Template;
 
-use super::message::{MessageFormContentTemplate, MessageFormTitleTemplate};
+use crate::{
+    models::{channel::ChannelModel, user::UserModel},
+    services::i18n::LocaleService,
+};
+
+use super::{
+    channel::{
+        ChannelFormTemplate, ChannelMemberFormTemplate, ChannelMembersTemplate,
+    },
+    filters,
+    message::MessageFormTemplate,
+};
 
 #[derive(Template)]
 #[template(path = "./dashboard.html")]
 pub struct DashboardTemplate<'a> {
     token: &'a str,
+    locale: &'a LocaleService,
     location: &'a str,
     name: Option<&'a str>,
-    message_form_title: MessageFormTitleTemplate<'a>,
-    message_form_content: MessageFormContentTemplate<'a>,
+    admin: bool,
+    channel_id: i32,
+    channel: &'a ChannelModel,
+    channels: &'a [ChannelModel],
+    members: Option<ChannelMembersTemplate<'a>>,
+    message_form: MessageFormTemplate<'a>,
+    channel_form: ChannelFormTemplate<'a>,
+    member_form: Option<ChannelMemberFormTemplate<'a>>,
+    message_id: Option<i32>,
 }
 
 impl<'a> DashboardTemplate<'a> {
-    pub fn new(token: &'a str, name: &'a str) -> Self {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        user: &'a UserModel,
+        channel: &'a ChannelModel,
+        channels: &'a [ChannelModel],
+        members: &'a [String],
+    ) -> Self {
+        let channel_id = channel.id.unwrap_or(ChannelModel::GENERAL);
         Self {
             token,
-            location: "Dashboard",
-            name: Some(name),
-            message_form_title: MessageFormTitleTemplate::new(token, 0, ""),
-            message_form_content: MessageFormContentTemplate::new(token, 0, ""),
+            locale,
+            location: "location-dashboard",
+            name: Some(&user.name),
+            admin: user.is_admin(),
+            channel_id,
+            channel,
+            channels,
+            members: channel
+                .private
+                .then(|| ChannelMembersTemplate::new(locale, members)),
+            message_form: MessageFormTemplate::new(
+                token, locale, 0, "", "", "",
+            ),
+            channel_form: ChannelFormTemplate::new(token, locale, ""),
+            member_form: channel.private.then(|| {
+                ChannelMemberFormTemplate::new(token, locale, channel_id, "")
+            }),
+            message_id: None,
         }
     }
+
+    pub fn message(mut self, message_id: Option<i32>) -> Self {
+        self.message_id = message_id;
+        self
+    }
 }
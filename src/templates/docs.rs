@@ -0,0 +1,13 @@
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "./docs.html")]
+pub struct DocsTemplate<'a> {
+    url: &'a str,
+}
+
+impl<'a> DocsTemplate<'a> {
+    pub fn new(url: &'a str) -> Self {
+        Self { url }
+    }
+}
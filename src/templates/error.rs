@@ -0,0 +1,40 @@
+use askama::Template;
+
+use crate::{models::user::UserModel, services::i18n::LocaleService};
+
+use super::filters;
+
+#[derive(Template)]
+#[template(path = "./error.html")]
+pub struct ErrorTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    location: &'a str,
+    name: Option<&'a str>,
+    admin: bool,
+    status: u16,
+    reason: &'a str,
+    message: &'a str,
+}
+
+impl<'a> ErrorTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        user: Option<&'a UserModel>,
+        status: u16,
+        reason: &'a str,
+        message: &'a str,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            location: "location-error",
+            name: user.map(|user| user.name.as_str()),
+            admin: user.is_some_and(UserModel::is_admin),
+            status,
+            reason,
+            message,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use askama::{Error, Template};
+
+use crate::{services::i18n::LocaleService, validation::FieldErrors};
+
+use super::filters;
+
+pub trait FormTemplate<'a>: Sized {
+    fn fields(self) -> Vec<FormFieldTemplate<'a>>;
+
+    fn field(self, name: &str) -> Option<FormFieldTemplate<'a>> {
+        self.fields().into_iter().find(|field| field.name == name)
+    }
+
+    fn render_oob(self) -> Result<String, Error> {
+        self.fields()
+            .into_iter()
+            .map(|field| field.oob().render())
+            .collect()
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./form/field.html")]
+pub struct FormFieldTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    form: String,
+    action: String,
+    name: &'static str,
+    label: &'static str,
+    kind: &'static str,
+    autocomplete: Option<&'static str>,
+    suggestions: Option<String>,
+    value: &'a str,
+    error: Option<&'a str>,
+    oob: bool,
+}
+
+impl<'a> FormFieldTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        form: &str,
+        name: &'static str,
+        label: &'static str,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            form: form.to_owned(),
+            action: String::new(),
+            name,
+            label,
+            kind: "text",
+            autocomplete: None,
+            suggestions: None,
+            value: "",
+            error: None,
+            oob: false,
+        }
+    }
+
+    pub fn textarea(mut self) -> Self {
+        self.kind = "textarea";
+        self
+    }
+
+    pub fn password(mut self) -> Self {
+        self.kind = "password";
+        self
+    }
+
+    pub fn autocomplete(mut self, autocomplete: &'static str) -> Self {
+        self.autocomplete = Some(autocomplete);
+        self
+    }
+
+    pub fn suggestions(mut self, suggestions: &str) -> Self {
+        self.suggestions = Some(suggestions.to_owned());
+        self
+    }
+
+    pub fn action(mut self, action: &str) -> Self {
+        action.clone_into(&mut self.action);
+        self
+    }
+
+    pub fn value(mut self, value: &'a str) -> Self {
+        self.value = value;
+        self
+    }
+
+    pub fn error(mut self, error: Option<&'a str>) -> Self {
+        self.error = error;
+        self
+    }
+
+    pub fn validate(self, errors: &'a FieldErrors) -> Self {
+        let error = errors.get(self.name);
+        self.error(error)
+    }
+
+    pub fn oob(mut self) -> Self {
+        self.oob = true;
+        self
+    }
+}
@@ -1,42 +1,135 @@
 use askama::Template;
 
-use crate::models::message::MessageModel;
+use crate::{
+    models::{message::MessageModel, tag::TagCountModel},
+    services::i18n::LocaleService,
+    validation::FieldErrors,
+};
+
+use super::{
+    filters,
+    form::{FormFieldTemplate, FormTemplate},
+};
 
 #[derive(Template)]
 #[template(path = "./message/show.html")]
 pub struct MessageShowTemplate<'a> {
     token: &'a str,
+    locale: &'a LocaleService,
     id: i32,
-    form_title: MessageFormTitleTemplate<'a>,
-    form_content: MessageFormContentTemplate<'a>,
+    message: &'a MessageModel,
+    form: MessageFormTemplate<'a>,
+    reply_form: MessageFormTemplate<'a>,
+    replies: Vec<MessageReplyTemplate<'a>>,
+    star: MessageStarTemplate<'a>,
+    pin: Option<MessagePinTemplate<'a>>,
 }
 
 impl<'a> MessageShowTemplate<'a> {
     pub fn new(
         token: &'a str,
+        locale: &'a LocaleService,
         id: i32,
-        title: &'a str,
-        content: &'a str,
+        message: &'a MessageModel,
+        thread: &'a [MessageModel],
+        tags: &'a str,
     ) -> Self {
         Self {
             token,
+            locale,
             id,
-            form_title: MessageFormTitleTemplate::new(token, id, title),
-            form_content: MessageFormContentTemplate::new(token, id, content),
+            message,
+            form: MessageFormTemplate::new(
+                token,
+                locale,
+                id,
+                &message.title,
+                &message.content,
+                tags,
+            ),
+            reply_form: MessageFormTemplate::reply(token, locale, id, "", ""),
+            replies: MessageReplyTemplate::replies(locale, id, thread),
+            star: MessageStarTemplate::new(token, locale, id, false),
+            pin: None,
         }
     }
+
+    pub fn star(mut self, starred: bool) -> Self {
+        self.star =
+            MessageStarTemplate::new(self.token, self.locale, self.id, starred);
+        self
+    }
+
+    pub fn pin(mut self, pinned: bool) -> Self {
+        self.pin = Some(MessagePinTemplate::new(
+            self.token,
+            self.locale,
+            self.id,
+            pinned,
+        ));
+        self
+    }
 }
 
 #[derive(Template)]
 #[template(path = "./message/index.html")]
 pub struct MessageIndexTemplate<'a> {
     token: &'a str,
+    locale: &'a LocaleService,
+    channel_id: i32,
     messages: &'a Vec<MessageModel>,
+    pinned: &'a [MessageModel],
+    starred: bool,
+    tags: &'a [TagCountModel],
+    filter: &'a [String],
 }
 
 impl<'a> MessageIndexTemplate<'a> {
-    pub fn new(token: &'a str, messages: &'a Vec<MessageModel>) -> Self {
-        Self { token, messages }
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        channel_id: i32,
+        messages: &'a Vec<MessageModel>,
+        pinned: &'a [MessageModel],
+        starred: bool,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            channel_id,
+            messages,
+            pinned,
+            starred,
+            tags: &[],
+            filter: &[],
+        }
+    }
+
+    pub fn tags(
+        mut self,
+        tags: &'a [TagCountModel],
+        filter: &'a [String],
+    ) -> Self {
+        self.tags = tags;
+        self.filter = filter;
+        self
+    }
+
+    fn active(&self, tag: &str) -> bool {
+        self.filter.iter().any(|name| name == tag)
+    }
+
+    fn toggle(&self, tag: &str) -> String {
+        let mut filter: Vec<&str> = self
+            .filter
+            .iter()
+            .map(String::as_str)
+            .filter(|name| *name != tag)
+            .collect();
+        if !self.active(tag) {
+            filter.push(tag);
+        }
+        filter.join(",")
     }
 }
 
@@ -44,61 +137,286 @@ impl<'a> MessageIndexTemplate<'a> {
 #[template(path = "./message/event.html")]
 pub struct MessageEventTemplate<'a> {
     token: &'a str,
+    locale: &'a LocaleService,
     message: &'a MessageModel,
 }
 
 impl<'a> MessageEventTemplate<'a> {
-    pub fn new(token: &'a str, message: &'a MessageModel) -> Self {
-        Self { token, message }
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        message: &'a MessageModel,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            message,
+        }
     }
 }
 
 #[derive(Template)]
-#[template(path = "./message/form_title.html")]
-pub struct MessageFormTitleTemplate<'a> {
+#[template(path = "./message/pins.html")]
+pub struct MessagePinsTemplate<'a> {
+    locale: &'a LocaleService,
+    pinned: &'a [MessageModel],
+}
+
+impl<'a> MessagePinsTemplate<'a> {
+    pub fn new(locale: &'a LocaleService, pinned: &'a [MessageModel]) -> Self {
+        Self { locale, pinned }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./message/star.html")]
+pub struct MessageStarTemplate<'a> {
     token: &'a str,
+    locale: &'a LocaleService,
     id: i32,
-    value: &'a str,
-    error: Option<&'a str>,
+    starred: bool,
 }
 
-impl<'a> MessageFormTitleTemplate<'a> {
-    pub fn new(token: &'a str, id: i32, value: &'a str) -> Self {
+impl<'a> MessageStarTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        id: i32,
+        starred: bool,
+    ) -> Self {
         Self {
             token,
+            locale,
             id,
-            value,
-            error: None,
+            starred,
         }
     }
-
-    pub fn validate(mut self, error: Option<&'a str>) -> Self {
-        self.error = error;
-        self
-    }
 }
 
 #[derive(Template)]
-#[template(path = "./message/form_content.html")]
-pub struct MessageFormContentTemplate<'a> {
+#[template(path = "./message/pin.html")]
+pub struct MessagePinTemplate<'a> {
     token: &'a str,
+    locale: &'a LocaleService,
     id: i32,
-    value: &'a str,
-    error: Option<&'a str>,
+    pinned: bool,
 }
 
-impl<'a> MessageFormContentTemplate<'a> {
-    pub fn new(token: &'a str, id: i32, value: &'a str) -> Self {
+impl<'a> MessagePinTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        id: i32,
+        pinned: bool,
+    ) -> Self {
         Self {
             token,
+            locale,
             id,
-            value,
-            error: None,
+            pinned,
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./message/reactions.html")]
+pub struct MessageReactionsTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    message: &'a MessageModel,
+}
+
+impl<'a> MessageReactionsTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        message: &'a MessageModel,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            message,
         }
     }
+}
+
+#[derive(Template)]
+#[template(path = "./message/form.html")]
+pub struct MessageFormTemplate<'a> {
+    title: FormFieldTemplate<'a>,
+    content: FormFieldTemplate<'a>,
+    tags: Option<FormFieldTemplate<'a>>,
+    preview: MessagePreviewTemplate<'a>,
+}
+
+impl<'a> MessageFormTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        id: i32,
+        title: &'a str,
+        content: &'a str,
+        tags: &'a str,
+    ) -> Self {
+        Self::build(
+            token,
+            locale,
+            &format!("message{id}"),
+            &format!("/message/{id}"),
+            title,
+            content,
+            Some(tags),
+        )
+    }
 
-    pub fn validate(mut self, error: Option<&'a str>) -> Self {
-        self.error = error;
+    pub fn reply(
+        token: &'a str,
+        locale: &'a LocaleService,
+        parent: i32,
+        title: &'a str,
+        content: &'a str,
+    ) -> Self {
+        Self::build(
+            token,
+            locale,
+            &format!("reply{parent}"),
+            &format!("/message/{parent}/reply"),
+            title,
+            content,
+            None,
+        )
+    }
+
+    fn build(
+        token: &'a str,
+        locale: &'a LocaleService,
+        form: &str,
+        action: &str,
+        title: &'a str,
+        content: &'a str,
+        tags: Option<&'a str>,
+    ) -> Self {
+        let validate = format!("{action}/validate");
+        Self {
+            title: FormFieldTemplate::new(
+                token,
+                locale,
+                form,
+                "title",
+                "field-title",
+            )
+            .action(&validate)
+            .value(title),
+            content: FormFieldTemplate::new(
+                token,
+                locale,
+                form,
+                "content",
+                "field-content",
+            )
+            .textarea()
+            .action(&validate)
+            .value(content),
+            tags: tags.map(|tags| {
+                FormFieldTemplate::new(
+                    token,
+                    locale,
+                    form,
+                    "tags",
+                    "field-tags",
+                )
+                .action(&validate)
+                .suggestions("/tags/suggestions")
+                .value(tags)
+            }),
+            preview: MessagePreviewTemplate::new(
+                token, locale, form, action, content,
+            ),
+        }
+    }
+
+    pub fn validate(mut self, errors: &'a FieldErrors) -> Self {
+        self.title = self.title.validate(errors);
+        self.content = self.content.validate(errors);
+        self.tags = self.tags.map(|tags| tags.validate(errors));
         self
     }
+
+    pub fn preview(self) -> MessagePreviewTemplate<'a> {
+        self.preview
+    }
+}
+
+impl<'a> FormTemplate<'a> for MessageFormTemplate<'a> {
+    fn fields(self) -> Vec<FormFieldTemplate<'a>> {
+        [Some(self.title), Some(self.content), self.tags]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./message/preview.html")]
+pub struct MessagePreviewTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    form: String,
+    action: String,
+    content: &'a str,
+}
+
+impl<'a> MessagePreviewTemplate<'a> {
+    fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        form: &str,
+        action: &str,
+        content: &'a str,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            form: form.to_owned(),
+            action: action.to_owned(),
+            content,
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./message/reply.html")]
+pub struct MessageReplyTemplate<'a> {
+    locale: &'a LocaleService,
+    message: &'a MessageModel,
+    replies: Vec<MessageReplyTemplate<'a>>,
+}
+
+impl<'a> MessageReplyTemplate<'a> {
+    pub fn new(
+        locale: &'a LocaleService,
+        message: &'a MessageModel,
+        thread: &'a [MessageModel],
+    ) -> Self {
+        Self {
+            locale,
+            message,
+            replies: message
+                .id
+                .map(|id| Self::replies(locale, id, thread))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn replies(
+        locale: &'a LocaleService,
+        id: i32,
+        thread: &'a [MessageModel],
+    ) -> Vec<Self> {
+        thread
+            .iter()
+            .filter(|message| message.parent_id == Some(id))
+            .map(|message| Self::new(locale, message, thread))
+            .collect()
+    }
 }
@@ -1,4 +1,40 @@
+pub mod audit;
 pub mod authentication;
+pub mod channel;
+pub mod conversation;
 pub mod dashboard;
+pub mod docs;
+pub mod error;
+pub mod form;
 pub mod message;
+pub mod notification;
+pub mod tag;
 pub mod toast;
+
+mod filters {
+    use askama::Result;
+    use fluent::FluentValue;
+
+    use crate::{markdown, services::i18n::LocaleService};
+
+    pub fn commonmark(source: &str) -> Result<String> {
+        Ok(markdown::render(source, &[]))
+    }
+
+    pub fn mentions(source: &str, mentions: &[String]) -> Result<String> {
+        Ok(markdown::render(source, mentions))
+    }
+
+    pub fn t(key: &str, locale: &LocaleService) -> Result<String> {
+        Ok(locale.get(key))
+    }
+
+    pub fn t_with<T: Clone + Into<FluentValue<'static>>>(
+        key: &str,
+        locale: &LocaleService,
+        name: &str,
+        value: &T,
+    ) -> Result<String> {
+        Ok(locale.format(key, &[(name, value.clone().into())]))
+    }
+}
@@ -0,0 +1,140 @@
+use askama::Template;
+
+use crate::{
+    models::{
+        notification::{NotificationModel, NotificationPreferenceModel},
+        user::UserModel,
+    },
+    services::i18n::LocaleService,
+};
+
+use super::filters;
+
+#[derive(Template)]
+#[template(path = "./notification.html")]
+pub struct NotificationIndexTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    location: &'a str,
+    name: Option<&'a str>,
+    admin: bool,
+    list: NotificationListTemplate<'a>,
+    preferences: &'a [NotificationPreferenceModel],
+}
+
+impl<'a> NotificationIndexTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        user: &'a UserModel,
+        list: NotificationListTemplate<'a>,
+        preferences: &'a [NotificationPreferenceModel],
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            location: "location-notifications",
+            name: Some(&user.name),
+            admin: user.is_admin(),
+            list,
+            preferences,
+        }
+    }
+
+    fn label(&self, kind: &str) -> String {
+        self.locale.get(&format!("notification-preference-{kind}"))
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./notification/list.html")]
+pub struct NotificationListTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    notifications: &'a [NotificationModel],
+    page: i64,
+    pages: i64,
+}
+
+impl<'a> NotificationListTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        notifications: &'a [NotificationModel],
+        page: i64,
+        pages: i64,
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            notifications,
+            page,
+            pages,
+        }
+    }
+
+    fn text(&self, notification: &NotificationModel) -> String {
+        text(self.locale, notification)
+    }
+
+    fn position(&self) -> String {
+        self.locale.format(
+            "notification-page",
+            &[("page", self.page.into()), ("pages", self.pages.into())],
+        )
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./notification/menu.html")]
+pub struct NotificationMenuTemplate<'a> {
+    token: &'a str,
+    locale: &'a LocaleService,
+    notifications: &'a [NotificationModel],
+}
+
+impl<'a> NotificationMenuTemplate<'a> {
+    pub fn new(
+        token: &'a str,
+        locale: &'a LocaleService,
+        notifications: &'a [NotificationModel],
+    ) -> Self {
+        Self {
+            token,
+            locale,
+            notifications,
+        }
+    }
+
+    fn text(&self, notification: &NotificationModel) -> String {
+        text(self.locale, notification)
+    }
+}
+
+#[derive(Template)]
+#[template(path = "./notification/unread.html")]
+pub struct NotificationUnreadTemplate {
+    count: i64,
+}
+
+impl NotificationUnreadTemplate {
+    pub fn new(count: i64) -> Self {
+        Self { count }
+    }
+}
+
+fn text(locale: &LocaleService, notification: &NotificationModel) -> String {
+    locale.format(
+        &notification.key(),
+        &[
+            (
+                "actor",
+                notification.actor.clone().unwrap_or_default().into(),
+            ),
+            (
+                "message",
+                notification.message_id.unwrap_or_default().into(),
+            ),
+        ],
+    )
+}
@@ -0,0 +1,13 @@
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "./tag/suggestions.html")]
+pub struct TagSuggestionsTemplate<'a> {
+    suggestions: &'a [String],
+}
+
+impl<'a> TagSuggestionsTemplate<'a> {
+    pub fn new(suggestions: &'a [String]) -> Self {
+        Self { suggestions }
+    }
+}
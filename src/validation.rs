@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tracing::instrument;
+use utoipa::ToSchema;
+use validator::{ValidateArgs, ValidationError, ValidationErrors};
+
+use crate::{config::Config, services::i18n::LocaleService};
+
+#[derive(Clone, Debug, Default, Serialize, ToSchema)]
+#[serde(transparent)]
+pub struct FieldErrors(BTreeMap<String, String>);
+
+impl FieldErrors {
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, field: &str, message: String) {
+        self.0.entry(field.to_owned()).or_insert(message);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &str> {
+        self.0.values().map(String::as_str)
+    }
+}
+
+impl FieldErrors {
+    fn localize(errors: ValidationErrors, locale: &LocaleService) -> Self {
+        Self(
+            errors
+                .field_errors()
+                .into_iter()
+                .filter_map(|(field, errors)| {
+                    let message = message(locale, &field, errors.first()?);
+                    Some((field.to_string(), message))
+                })
+                .collect(),
+        )
+    }
+}
+
+#[instrument(level = "trace", skip(value))]
+pub fn validate<'a, T>(
+    value: &'a T,
+    config: &'a Config,
+    locale: &LocaleService,
+) -> Result<(), FieldErrors>
+where
+    T: ValidateArgs<'a, Args = &'a Config>,
+{
+    value
+        .validate_with_args(config)
+        .map_err(|errors| FieldErrors::localize(errors, locale))
+}
+
+pub fn not_empty(value: &str) -> Result<(), ValidationError> {
+    min_length(value, 1)
+}
+
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if !value.is_empty() && value.trim().is_empty() {
+        return Err(ValidationError::new("blank"));
+    }
+    Ok(())
+}
+
+pub fn min_length(value: &str, min: usize) -> Result<(), ValidationError> {
+    if value.chars().count() < min {
+        let mut error = ValidationError::new("min_length");
+        error.add_param("min".into(), &min);
+        return Err(error);
+    }
+    Ok(())
+}
+
+pub fn max_length(value: &str, max: usize) -> Result<(), ValidationError> {
+    if max < value.chars().count() {
+        let mut error = ValidationError::new("max_length");
+        error.add_param("max".into(), &max);
+        return Err(error);
+    }
+    Ok(())
+}
+
+fn message(
+    locale: &LocaleService,
+    field: &str,
+    error: &ValidationError,
+) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+    let field = locale.get(&format!("field-{field}"));
+    let count = |key| {
+        error
+            .params
+            .get(key)
+            .and_then(|value| value.as_u64())
+            .unwrap_or_default()
+    };
+    match error.code.as_ref() {
+        "blank" => {
+            locale.format("validation-blank", &[("field", field.into())])
+        }
+        "min_length" => locale.format(
+            "validation-min-length",
+            &[("field", field.into()), ("min", count("min").into())],
+        ),
+        "max_length" => locale.format(
+            "validation-max-length",
+            &[("field", field.into()), ("max", count("max").into())],
+        ),
+        "tags" => locale.format(
+            "validation-tags",
+            &[
+                ("max", count("max").into()),
+                ("length", count("length").into()),
+            ],
+        ),
+        _ => locale.format("validation-invalid", &[("field", field.into())]),
+    }
+}